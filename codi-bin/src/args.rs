@@ -1,9 +1,69 @@
+/**
+    Source of the colors to process.
+*/
+pub enum Input {
+    /// CSS color from the arguments.
+    Color(String),
+    /// Colors from stdin, one per line.
+    Stdin,
+    /// Colors from file, one per line.
+    File(std::path::PathBuf),
+}
+
+/**
+    Subcommand to run.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Command {
+    /// Find closest named colors for each input color.
+    #[default]
+    Find,
+    /// Find distance between two input colors.
+    Diff,
+    /// Convert input colors to other color spaces.
+    Convert,
+}
+
+impl Command {
+    /**
+        Name of the command for the error messages.
+    */
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Find => "color search",
+            Self::Diff => "diff",
+            Self::Convert => "convert",
+        }
+    }
+}
+
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Args {
-    pub color: Option<codi_core::color_space::Rgb>,
+    pub command: Command,
+    pub inputs: Vec<Input>,
     pub help: bool,
     pub version: bool,
     pub all_html: bool,
+    pub list_algorithms: bool,
+    /// Empty if all algorithms should be used.
+    pub algorithms: Vec<&'static dyn codi_core::color_dist::ColorDistance>,
+    pub palette: Option<std::path::PathBuf>,
+    pub format: crate::output::Format,
+    /// Number of the closest colors to show, 1 if not specified.
+    pub top: Option<std::num::NonZeroUsize>,
+    /// Maximum distance of the colors to show.
+    pub within: Option<f32>,
+    /// Maximum allowed distance between colors for [`Command::Diff`].
+    pub max: Option<f32>,
+    /// Empty if colors should be converted to all spaces.
+    pub spaces: Vec<crate::convert::Space>,
+    /// Number of decimal places for [`Command::Convert`].
+    pub precision: Option<usize>,
+    /// Reference white for [`Command::Convert`], D65 if not specified.
+    pub illuminant: Option<codi_core::illuminant::Illuminant>,
+    /// Strategy for the input colors outside of the sRGB gamut.
+    pub gamut: codi_core::gamut::GamutMapping,
 }
 
 #[allow(clippy::module_name_repetitions, clippy::missing_errors_doc)]
@@ -14,14 +74,95 @@ pub fn parse_cli_args() -> Result<Args, lexopt::Error> {
 
     let mut parser = lexopt::Parser::from_env();
     while let Some(arg) = parser.next()? {
+        // Subcommand can only be the first value.
+        let first_value = args.command == Command::Find && args.inputs.is_empty();
         match arg {
             Long("help") => args.help = true,
             Long("version") => args.version = true,
             Long("all-html") => args.all_html = true,
-            Value(color_str) => args.color = Some(color_str.parse()?),
+            Long("list-algorithms") => args.list_algorithms = true,
+            Long("algorithm") => {
+                let algorithm = parser.value()?.parse_with(|name| {
+                    codi_core::color_dist::find_algorithm(name)
+                        .ok_or("unknown algorithm, see --list-algorithms")
+                })?;
+                args.algorithms.push(algorithm);
+            }
+            Long("palette") => args.palette = Some(parser.value()?.into()),
+            Long("top") => args.top = Some(parser.value()?.parse()?),
+            Long("within") => args.within = Some(parser.value()?.parse_with(parse_distance)?),
+            Long("max") => args.max = Some(parser.value()?.parse_with(parse_distance)?),
+            Long("to") => args.spaces.push(parser.value()?.parse()?),
+            Long("precision") => args.precision = Some(parser.value()?.parse()?),
+            Long("illuminant") => {
+                let illuminant = parser.value()?.parse_with(|name| {
+                    codi_core::illuminant::Illuminant::find(name)
+                        .ok_or("unknown illuminant, expected one of: A, D50, D55, D65, D75, E, F2, F7, F11")
+                })?;
+                args.illuminant = Some(illuminant);
+            }
+            Long("gamut") => {
+                let gamut = parser.value()?.parse_with(|name| {
+                    codi_core::gamut::GamutMapping::ALL
+                        .into_iter()
+                        .find(|gamut| gamut.name().eq_ignore_ascii_case(name))
+                        .ok_or("unknown gamut mapping, expected clip or css")
+                })?;
+                args.gamut = gamut;
+            }
+            Long("format") => args.format = parser.value()?.parse()?,
+            Long("input") => args.inputs.push(Input::File(parser.value()?.into())),
+            Value(value) if first_value && value == "diff" => args.command = Command::Diff,
+            Value(value) if first_value && value == "convert" => args.command = Command::Convert,
+            Value(value) if value == "-" => args.inputs.push(Input::Stdin),
+            Value(color) => args.inputs.push(Input::Color(color.string()?)),
             _ => return Err(arg.unexpected()),
         }
     }
 
+    check_command_options(&args)?;
     Ok(args)
 }
+
+/**
+    Reject options which would be silently ignored by the selected command.
+*/
+fn check_command_options(args: &Args) -> Result<(), lexopt::Error> {
+    use Command::{Convert, Diff, Find};
+
+    let options: [(&str, &[Command], bool); 9] = [
+        ("--algorithm", &[Find, Diff], !args.algorithms.is_empty()),
+        ("--palette", &[Find], args.palette.is_some()),
+        (
+            "--format",
+            &[Find],
+            args.format != crate::output::Format::Table,
+        ),
+        ("--top", &[Find], args.top.is_some()),
+        ("--within", &[Find], args.within.is_some()),
+        ("--max", &[Diff], args.max.is_some()),
+        ("--to", &[Convert], !args.spaces.is_empty()),
+        ("--precision", &[Convert], args.precision.is_some()),
+        ("--illuminant", &[Convert], args.illuminant.is_some()),
+    ];
+    for (option, commands, used) in options {
+        if used && !commands.contains(&args.command) {
+            let supported: Vec<_> = commands.iter().map(|command| command.name()).collect();
+            return Err(format!(
+                "{option} cannot be used with {}, only with {}",
+                args.command.name(),
+                supported.join(" and ")
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn parse_distance(value: &str) -> Result<f32, &'static str> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|distance| distance.is_finite() && *distance >= 0.0)
+        .ok_or("expected non-negative distance")
+}
@@ -0,0 +1,192 @@
+use std::io::Write;
+
+use codi_core::color_space::{Cielab, Hsl, Hsv, Hwb, Oklab, Oklch};
+use codi_core::gamut::Mapped;
+use codi_core::illuminant::{Adaptation, Illuminant};
+use codi_core::rgb_space::RgbSpace;
+
+/**
+    Parameters of the conversion.
+*/
+pub struct Options {
+    /// Number of decimal places.
+    pub precision: usize,
+    /// Reference white of XYZ and Lab, D65 colors are adapted to it with
+    /// Bradford transform.
+    pub white: Illuminant,
+}
+
+/**
+    Color space to convert colors to.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Space {
+    Hex,
+    Rgb,
+    Hsl,
+    Hsv,
+    Hwb,
+    Xyz,
+    Lab,
+    Oklab,
+    Oklch,
+    DisplayP3,
+    Rec2020,
+    A98Rgb,
+    ProphotoRgb,
+}
+
+impl Space {
+    pub const ALL: [Self; 13] = [
+        Self::Hex,
+        Self::Rgb,
+        Self::Hsl,
+        Self::Hsv,
+        Self::Hwb,
+        Self::Xyz,
+        Self::Lab,
+        Self::Oklab,
+        Self::Oklch,
+        Self::DisplayP3,
+        Self::Rec2020,
+        Self::A98Rgb,
+        Self::ProphotoRgb,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Rgb => "rgb",
+            Self::Hsl => "hsl",
+            Self::Hsv => "hsv",
+            Self::Hwb => "hwb",
+            Self::Xyz => "xyz",
+            Self::Lab => "lab",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+            Self::DisplayP3 => RgbSpace::DISPLAY_P3.name(),
+            Self::Rec2020 => RgbSpace::REC2020.name(),
+            Self::A98Rgb => RgbSpace::ADOBE_RGB.name(),
+            Self::ProphotoRgb => RgbSpace::PROPHOTO_RGB.name(),
+        }
+    }
+
+    /**
+        Formats color in this space. Components are separated with spaces and
+        have `precision` decimal places. Hue is in degrees, other components
+        of HSL, HSV and HWB are in percents, channels of wide-gamut RGB spaces
+        are in range [0, 1]. Only hex and RGB are rounded to 8-bit channels.
+    */
+    pub fn format(self, color: Mapped, options: &Options) -> String {
+        let (srgb, xyz) = (color.srgb, color.xyz());
+        let color = color.color;
+        let adapted = || xyz.adapt(Illuminant::D65, options.white, Adaptation::Bradford);
+        let components = match self {
+            Self::Hex => return format!("{color:X}"),
+            Self::Rgb => return format!("{} {} {}", color.r, color.g, color.b),
+            Self::Hsl => {
+                let hsl = Hsl::from_srgb(srgb);
+                [hsl.h(), hsl.s() * 100.0, hsl.l() * 100.0]
+            }
+            Self::Hsv => {
+                let hsv = Hsv::from_srgb(srgb);
+                [hsv.h(), hsv.s() * 100.0, hsv.v() * 100.0]
+            }
+            Self::Hwb => {
+                let hwb = Hwb::from_srgb(srgb);
+                [hwb.h(), hwb.w() * 100.0, hwb.b() * 100.0]
+            }
+            Self::Xyz => {
+                let xyz = adapted();
+                [xyz.x(), xyz.y(), xyz.z()]
+            }
+            Self::Lab => {
+                let lab = Cielab::from_xyz(adapted(), options.white);
+                [lab.l(), lab.a(), lab.b()]
+            }
+            Self::Oklab => {
+                let lab = Oklab::from(xyz);
+                [lab.l(), lab.a(), lab.b()]
+            }
+            Self::Oklch => {
+                let lch = Oklch::from(Oklab::from(xyz));
+                [lch.l(), lch.c(), lch.h()]
+            }
+            Self::DisplayP3 => RgbSpace::DISPLAY_P3.from_xyz(xyz),
+            Self::Rec2020 => RgbSpace::REC2020.from_xyz(xyz),
+            Self::A98Rgb => RgbSpace::ADOBE_RGB.from_xyz(xyz),
+            Self::ProphotoRgb => RgbSpace::PROPHOTO_RGB.from_xyz(xyz),
+        };
+        components
+            .map(|value| number(value, options.precision))
+            .join(" ")
+    }
+}
+
+impl std::str::FromStr for Space {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|space| space.name().eq_ignore_ascii_case(value))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|space| space.name()).collect();
+                format!(
+                    "unknown color space \"{value}\", expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/**
+    Prints colors in each of the spaces. Single color is printed as one row
+    per space, several colors as one row per color.
+
+    # Errors
+
+    Returns [`Err`] if writing to `writer` fails.
+*/
+pub fn write_table<T: Write>(
+    writer: &mut T,
+    colors: &[Mapped],
+    spaces: &[Space],
+    options: &Options,
+) -> std::io::Result<()> {
+    use tabled::builder::Builder;
+
+    let mut table = Builder::default();
+    if let [color] = colors {
+        for space in spaces {
+            table.push_record([space.name().into(), space.format(*color, options)]);
+        }
+    } else {
+        table.push_record(
+            ["Color".into(), String::new()]
+                .into_iter()
+                .chain(spaces.iter().map(|space| space.name().into())),
+        );
+        for &color in colors {
+            table.push_record(
+                [format!("{:X}", color.color), crate::rgb_block(color.color)]
+                    .into_iter()
+                    .chain(spaces.iter().map(|space| space.format(color, options))),
+            );
+        }
+    }
+
+    let table = table.build().to_string() + "\n";
+    writer.write_all(table.as_bytes())
+}
+
+/**
+    Formats number with given decimal places without negative zero.
+*/
+fn number(value: f32, precision: usize) -> String {
+    let res = format!("{value:.precision$}");
+    match res.strip_prefix('-') {
+        Some(abs) if abs.bytes().all(|ch| ch == b'0' || ch == b'.') => abs.to_owned(),
+        _ => res,
+    }
+}
@@ -1,7 +1,45 @@
 pub mod args;
+pub mod convert;
+pub mod output;
+
+use codi_core::color_dist::{ColorDistance, Match, Perception};
+use codi_core::color_space::Rgb;
+use codi_core::palette::{Palette, PaletteColor};
 
 /**
-    Find closest named html color to target color using all algorithms.
+    Parameters of the closest color search.
+*/
+pub struct Search<'a> {
+    pub algorithms: &'a [&'a dyn ColorDistance],
+    pub palette: &'a Palette,
+    /// Maximum number of the closest colors to find for each algorithm.
+    pub top: usize,
+    /// Find only colors within this distance from the target.
+    pub within: Option<f32>,
+}
+
+impl Search<'_> {
+    /**
+        Find closest colors sorted by distance in ascending order. Distance is
+        in units of the algorithm.
+    */
+    pub fn find(&self, algo: &dyn ColorDistance, target: Rgb) -> Vec<(PaletteColor<'_>, f32)> {
+        let mut found = self.within.map_or_else(
+            || self.palette.find_k_closest(algo, target, self.top),
+            |max_distance| self.palette.find_within(algo, target, max_distance),
+        );
+        found.truncate(self.top);
+        found
+            .into_iter()
+            .map(|Match { color, distance }| (color, *distance))
+            .collect()
+    }
+}
+
+/**
+    Find closest named colors from the palette to target color using each
+    algorithm. Prints one row per found color with its distance and how different
+    it looks from the target color.
 
     # Errors
 
@@ -9,34 +47,283 @@ pub mod args;
 */
 pub fn find_closest_all_algs<T: std::io::Write>(
     writer: &mut T,
-    orig_color: codi_core::color_space::Rgb,
+    orig_color: Rgb,
+    search: &Search,
 ) -> std::io::Result<()> {
-    use codi_core::html_color::{HtmlColor, find_closest, find_exact};
     use tabled::builder::Builder;
 
     let mut table = Builder::default();
-    table.push_record(["Algorithm", "HTML color", "Hex", ""]);
+    table.push_record([
+        "Algorithm",
+        "Color name",
+        "Hex",
+        "",
+        "Distance",
+        "Difference",
+    ]);
     table.push_record([
         "> Original color".into(),
-        find_exact(orig_color).unwrap_or("unknown").into(),
+        search
+            .palette
+            .find_exact(orig_color)
+            .unwrap_or("unknown")
+            .into(),
         format!("{orig_color:X}"),
         rgb_block(orig_color),
+        String::new(),
+        String::new(),
     ]);
 
-    for algo in codi_core::color_dist::ALGORITHMS {
-        let HtmlColor { name, color } = find_closest(algo, orig_color);
-        table.push_record([
-            algo.to_string(),
-            name.into(),
-            format!("{color:X}"),
-            rgb_block(color),
-        ]);
+    for algo in search.algorithms {
+        let found = search.find(*algo, orig_color);
+        if found.is_empty() {
+            table.push_record([algo.to_string(), "-".into()]);
+        }
+        for (rank, (PaletteColor { name, color }, distance)) in found.into_iter().enumerate() {
+            // Show algorithm only once for all of its colors.
+            let algo_name = if rank == 0 {
+                algo.to_string()
+            } else {
+                String::new()
+            };
+            table.push_record([
+                algo_name,
+                name.into(),
+                format!("{color:X}"),
+                rgb_block(color),
+                format!("{distance:.2}"),
+                Perception::between(orig_color, color).to_string(),
+            ]);
+        }
+    }
+
+    let table = table.build().to_string() + "\n";
+    writer.write_all(table.as_bytes())
+}
+
+/**
+    Find closest named colors from the palette for each color using each
+    algorithm. Prints one row per color.
+
+    # Errors
+
+    Returns [`Err`] if writing to `writer` fails.
+*/
+pub fn find_closest_batch<T: std::io::Write>(
+    writer: &mut T,
+    colors: &[Rgb],
+    search: &Search,
+) -> std::io::Result<()> {
+    use tabled::builder::Builder;
+
+    let mut table = Builder::default();
+    table.push_record(
+        ["Color".into(), String::new()]
+            .into_iter()
+            .chain(search.algorithms.iter().map(ToString::to_string)),
+    );
+
+    for &orig_color in colors {
+        let names = search.algorithms.iter().map(|algo| {
+            let names: Vec<_> = search
+                .find(*algo, orig_color)
+                .into_iter()
+                .map(|(closest, _)| closest.name)
+                .collect();
+            if names.is_empty() {
+                String::from("-")
+            } else {
+                names.join(", ")
+            }
+        });
+        table.push_record(
+            [format!("{orig_color:X}"), rgb_block(orig_color)]
+                .into_iter()
+                .chain(names),
+        );
     }
 
     let table = table.build().to_string() + "\n";
     writer.write_all(table.as_bytes())
 }
 
+/**
+    Find distance between two colors using each algorithm. If `max` is given,
+    distances of the algorithms paired with `true` are checked to be not
+    greater than it, others are only shown.
+
+    # Returns
+    `false` if any of the checked distances is greater than `max`.
+
+    # Errors
+
+    Returns [`Err`] if writing to `writer` fails.
+*/
+pub fn diff<T: std::io::Write>(
+    writer: &mut T,
+    (c1, c2): (Rgb, Rgb),
+    algorithms: &[(&dyn ColorDistance, bool)],
+    max: Option<f32>,
+) -> std::io::Result<bool> {
+    use tabled::builder::Builder;
+    use tabled::settings::Span;
+
+    let mut header = vec!["Algorithm", "Distance", "Difference"];
+    if max.is_some() {
+        header.push("Result");
+    }
+
+    let mut table = Builder::default();
+    table.push_record(header.iter().copied());
+    table.push_record([
+        "> Colors".into(),
+        format!("{c1:X} {} {} {c2:X}", rgb_block(c1), rgb_block(c2)),
+    ]);
+
+    let mut passed = true;
+    let difference = Perception::between(c1, c2).to_string();
+    for &(algo, checked) in algorithms {
+        let distance = *algo.dist(c1, c2);
+        let mut record = vec![
+            algo.to_string(),
+            format!("{distance:.2}"),
+            difference.clone(),
+        ];
+        match max {
+            Some(max) if checked => {
+                let ok = distance <= max;
+                passed &= ok;
+                record.push(if ok { "pass" } else { "fail" }.into());
+            }
+            Some(_) => record.push(String::new()),
+            None => {}
+        }
+        table.push_record(record);
+    }
+
+    let mut table = table.build();
+    // Show both colors across distance columns.
+    table.modify((1, 1), Span::column(if max.is_some() { 3 } else { 2 }));
+    let table = table.to_string() + "\n";
+    writer.write_all(table.as_bytes())?;
+    Ok(passed)
+}
+
+/**
+    Collects colors from all inputs preserving their order. Colors outside of
+    the sRGB gamut are mapped with `gamut` and reported to `warnings`.
+
+    # Errors
+
+    Returns [`Err`] if any of the inputs cannot be read or has invalid color.
+*/
+pub fn read_inputs<W: std::io::Write>(
+    inputs: Vec<args::Input>,
+    gamut: codi_core::gamut::GamutMapping,
+    warnings: &mut W,
+) -> Result<Vec<codi_core::gamut::Mapped>, Box<dyn std::error::Error>> {
+    let mut colors = vec![];
+    for input in inputs {
+        match input {
+            args::Input::Color(color) => colors.push(
+                parse_color(&color, gamut, warnings)
+                    .map_err(|err| format!("cannot parse argument {color:?}: {err}"))?,
+            ),
+            args::Input::Stdin => colors.extend(
+                read_colors(std::io::stdin().lock(), gamut, warnings)
+                    .map_err(|err| format!("stdin: {err}"))?,
+            ),
+            args::Input::File(path) => colors.extend(
+                std::fs::File::open(&path)
+                    .map_err(Into::into)
+                    .and_then(|file| read_colors(std::io::BufReader::new(file), gamut, warnings))
+                    .map_err(|err| format!("{}: {err}", path.display()))?,
+            ),
+        }
+    }
+    Ok(colors)
+}
+
+/**
+    Reads colors from the reader, one per line. Empty lines are skipped.
+    Colors outside of the sRGB gamut are mapped with `gamut` and reported to
+    `warnings`.
+
+    # Errors
+
+    Returns [`Err`] if reading fails or line is not a valid color.
+*/
+pub fn read_colors<R: std::io::BufRead, W: std::io::Write>(
+    reader: R,
+    gamut: codi_core::gamut::GamutMapping,
+    warnings: &mut W,
+) -> Result<Vec<codi_core::gamut::Mapped>, Box<dyn std::error::Error>> {
+    let mut colors = vec![];
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let color = parse_color(&line, gamut, warnings)
+            .map_err(|err| format!("line {}: cannot parse {line:?}: {err}", idx + 1))?;
+        colors.push(color);
+    }
+    Ok(colors)
+}
+
+fn parse_color<W: std::io::Write>(
+    color: &str,
+    gamut: codi_core::gamut::GamutMapping,
+    warnings: &mut W,
+) -> Result<codi_core::gamut::Mapped, Box<dyn std::error::Error>> {
+    let mapped = codi_core::css::parse_mapped(color, gamut)?;
+    if mapped.clamped {
+        writeln!(
+            warnings,
+            "warning: {:?} is outside of sRGB gamut, mapped to {:X} with {gamut}",
+            color.trim(),
+            mapped.color
+        )?;
+    }
+    Ok(mapped)
+}
+
+/**
+    Reads palette from the file. Format is guessed from the file extension or
+    detected from the content.
+
+    # Errors
+
+    Returns [`Err`] if file cannot be read or has invalid format.
+*/
+pub fn read_palette(
+    path: &std::path::Path,
+) -> Result<codi_core::palette::Palette, Box<dyn std::error::Error>> {
+    use codi_core::palette::{Error, Format, Palette};
+
+    let content = std::fs::read(path)
+        .map_err(|err| format!("cannot read palette {}: {err}", path.display()))?;
+    let format = path
+        .extension()
+        .and_then(|ext| Format::from_extension(&ext.to_string_lossy()))
+        .or_else(|| Format::detect(&content))
+        .ok_or(Error::UnknownFormat);
+    format
+        .and_then(|format| Palette::parse_format(&content, format))
+        .map_err(|err| format!("invalid palette {}: {err}", path.display()).into())
+}
+
+pub fn print_all_algorithms() -> tabled::Table {
+    use tabled::builder::Builder;
+
+    let mut table = Builder::default();
+    table.push_record(["Name", "Algorithm"]);
+    for (name, algo) in codi_core::color_dist::REGISTRY {
+        table.push_record([name.into(), algo.to_string()]);
+    }
+    table.build()
+}
+
 pub fn print_all_html_colors() -> tabled::Table {
     use tabled::builder::Builder;
 
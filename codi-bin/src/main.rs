@@ -1,5 +1,11 @@
+use codi_bin::args::Command;
+use codi_bin::output::Format;
+use codi_core::color_dist::ColorDistance;
+
 const PKG_NAME: &str = env!("CARGO_BIN_NAME");
 const VERSION: &str = concat!(env!("CARGO_BIN_NAME"), " v", env!("CARGO_PKG_VERSION"));
+/// `--max` is ΔE, so diff checks only CIEDE2000 unless algorithms are given.
+const MAX_ALGORITHM: &str = "ciede2000";
 
 fn main() {
     let mut stdout = std::io::stdout();
@@ -21,13 +27,95 @@ fn main() {
         println!("{}", codi_bin::print_all_html_colors());
         return;
     }
+    if args.list_algorithms {
+        println!("{}", codi_bin::print_all_algorithms());
+        return;
+    }
 
-    if let Some(color) = args.color {
-        codi_bin::find_closest_all_algs(&mut stdout, color).unwrap();
-    } else {
+    let palette = args.palette.as_deref().map_or_else(
+        || Ok(codi_core::palette::Palette::html()),
+        codi_bin::read_palette,
+    );
+    let palette = palette.unwrap_or_else(|err| {
+        eprintln!("{PKG_NAME}: {err}");
+        std::process::exit(1);
+    });
+
+    if args.inputs.is_empty() {
         eprintln!("{}", help_message());
         std::process::exit(1);
     }
+
+    let mapped = codi_bin::read_inputs(args.inputs, args.gamut, &mut std::io::stderr())
+        .unwrap_or_else(|err| {
+            eprintln!("{PKG_NAME}: {err}");
+            std::process::exit(1);
+        });
+    let colors: Vec<_> = mapped.iter().map(|mapped| mapped.color).collect();
+
+    let algorithms = if args.algorithms.is_empty() {
+        &codi_core::color_dist::ALGORITHMS[..]
+    } else {
+        &args.algorithms
+    };
+
+    if args.command == Command::Convert {
+        let spaces = if args.spaces.is_empty() {
+            &codi_bin::convert::Space::ALL[..]
+        } else {
+            &args.spaces
+        };
+        let options = codi_bin::convert::Options {
+            precision: args.precision.unwrap_or(3),
+            white: args
+                .illuminant
+                .unwrap_or(codi_core::illuminant::Illuminant::D65),
+        };
+        codi_bin::convert::write_table(&mut stdout, &mapped, spaces, &options).unwrap();
+        return;
+    }
+
+    if args.command == Command::Diff {
+        let [c1, c2] = colors[..] else {
+            eprintln!("{PKG_NAME}: diff expects exactly two colors");
+            std::process::exit(1);
+        };
+        let checked: Vec<(&dyn ColorDistance, bool)> = if args.algorithms.is_empty() {
+            codi_core::color_dist::REGISTRY
+                .iter()
+                .map(|&(name, algo)| (algo, name == MAX_ALGORITHM))
+                .collect()
+        } else {
+            args.algorithms.iter().map(|&algo| (algo, true)).collect()
+        };
+        let passed = codi_bin::diff(&mut stdout, (c1, c2), &checked, args.max).unwrap();
+        if !passed {
+            std::process::exit(2);
+        }
+        return;
+    }
+
+    // Show all colors within the distance unless their number is limited.
+    let default_top = if args.within.is_some() { usize::MAX } else { 1 };
+    let search = codi_bin::Search {
+        algorithms,
+        palette: &palette,
+        top: args.top.map_or(default_top, std::num::NonZeroUsize::get),
+        within: args.within,
+    };
+
+    let records = || codi_bin::output::records(&colors, &search);
+    match (args.format, &colors[..]) {
+        (Format::Table, [color]) => {
+            codi_bin::find_closest_all_algs(&mut stdout, *color, &search).unwrap();
+        }
+        (Format::Table, _) => {
+            codi_bin::find_closest_batch(&mut stdout, &colors, &search).unwrap();
+        }
+        (Format::Json, _) => codi_bin::output::write_json(&mut stdout, &records()).unwrap(),
+        (Format::Csv, _) => codi_bin::output::write_csv(&mut stdout, &records()).unwrap(),
+        (Format::Tsv, _) => codi_bin::output::write_tsv(&mut stdout, &records()).unwrap(),
+    }
 }
 
 fn help_message() -> String {
@@ -37,15 +125,53 @@ fn help_message() -> String {
 Find closest named html color.
 
 Usage:
-    codi <color>
+    codi [options] <color>...
+    codi [options] -
+    codi [options] diff <color1> <color2>
+    codi [options] convert <color>...
 
 Args:
-    <color> Hex color, e.g. \"#000000\" or \"ffffff\"
+    <color> CSS color, e.g. \"#000000\", \"ffffff\", \"rgb(0 0 0)\",
+            \"oklch(70% 0.1 200)\", \"color(display-p3 1 0.5 0)\"
+            or \"coral\"
+    -       Read colors from stdin, one per line
 
 Options:
     --help                  Prints help information
     --version               Prints version
     --all-html              Displays all named html color
+    --input <file>          Read colors from file, one per line
+    --format <format>       Output format: table (default), json, csv or tsv
+    --algorithm <name>      Use only given algorithm, can be repeated
+    --list-algorithms       Displays names of all algorithms
+    --top <n>               Show n closest colors for each algorithm
+    --within <distance>     Show all colors within the distance, e.g. 2.0
+                            for barely noticeable difference
+    --max <distance>        Exit with code 2 if diff finds greater ΔE,
+                            checked with CIEDE2000 unless --algorithm
+                            is given
+    --to <space>            Convert only to given space, can be repeated:
+                            hex, rgb, hsl, hsv, hwb, xyz, lab, oklab,
+                            oklch, display-p3, rec2020, a98-rgb
+                            or prophoto-rgb
+    --precision <n>         Decimal places of converted colors, default 3
+    --illuminant <name>     Reference white of converted xyz and lab:
+                            D65 (default), D50, D55, D75, A, E, F2, F7
+                            or F11
+    --gamut <mapping>       Mapping of colors outside of sRGB gamut:
+                            css (default) reduces chroma, clip clamps
+                            channels
+    --palette <file>        Use colors from file instead of html colors:
+                            GIMP .gpl, Adobe .aco/.ase, X11 rgb.txt or
+                            lines in format \"name rgb(r, g, b)\"
+
+Distances:
+    Each algorithm has its own units, so their distances are not
+    comparable: euclidean and euclidean-improved in RGB channel values,
+    oklab in Oklab distance multiplied by 100, ictcp in ΔE ITP, which is
+    ICtCp distance multiplied by 720, jzazbz in ΔEz multiplied by 450
+    and others in ΔE. Difference column is classified with CIEDE2000
+    for all algorithms.
     "
     )
 }
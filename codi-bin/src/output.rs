@@ -0,0 +1,169 @@
+use std::fmt::Write as _;
+use std::io::Write;
+
+use codi_core::color_dist::ColorDistance;
+use codi_core::color_space::Rgb;
+use codi_core::palette::PaletteColor;
+
+use crate::Search;
+
+/**
+    Output format of the closest colors.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(format!(
+                "unknown format \"{value}\", expected one of: table, json, csv, tsv"
+            )),
+        }
+    }
+}
+
+/**
+    One of the closest colors from the palette found by one algorithm.
+*/
+pub struct Record<'a> {
+    pub input: Rgb,
+    pub algorithm: &'a dyn ColorDistance,
+    pub name: &'a str,
+    pub color: Rgb,
+    pub distance: f32,
+}
+
+/**
+    Finds closest colors for each input color using each algorithm.
+*/
+pub fn records<'a>(colors: &[Rgb], search: &'a Search) -> Vec<Record<'a>> {
+    let mut records = vec![];
+    for &input in colors {
+        for &algorithm in search.algorithms {
+            for (PaletteColor { name, color }, distance) in search.find(algorithm, input) {
+                records.push(Record {
+                    input,
+                    algorithm,
+                    name,
+                    color,
+                    distance,
+                });
+            }
+        }
+    }
+    records
+}
+
+/**
+    Writes records as JSON array of objects.
+
+    # Errors
+
+    Returns [`Err`] if writing to `writer` fails.
+*/
+pub fn write_json<T: Write>(writer: &mut T, records: &[Record]) -> std::io::Result<()> {
+    writeln!(writer, "[")?;
+    for (idx, record) in records.iter().enumerate() {
+        let separator = if idx + 1 == records.len() { "" } else { "," };
+        writeln!(
+            writer,
+            "  {{\"input\": \"{:X}\", \"algorithm\": {}, \"name\": {}, \"hex\": \"{:X}\", \"distance\": {}}}{separator}",
+            record.input,
+            json_string(&record.algorithm.to_string()),
+            json_string(record.name),
+            record.color,
+            record.distance,
+        )?;
+    }
+    writeln!(writer, "]")
+}
+
+/**
+    Writes records as CSV with header, see RFC 4180.
+
+    # Errors
+
+    Returns [`Err`] if writing to `writer` fails.
+*/
+pub fn write_csv<T: Write>(writer: &mut T, records: &[Record]) -> std::io::Result<()> {
+    write_separated(writer, records, ",", csv_field)
+}
+
+/**
+    Writes records as tab-separated values with header. Tabs and newlines
+    inside of the fields are replaced with spaces.
+
+    # Errors
+
+    Returns [`Err`] if writing to `writer` fails.
+*/
+pub fn write_tsv<T: Write>(writer: &mut T, records: &[Record]) -> std::io::Result<()> {
+    write_separated(writer, records, "\t", |field| {
+        field.replace(['\t', '\n', '\r'], " ")
+    })
+}
+
+fn write_separated<T: Write>(
+    writer: &mut T,
+    records: &[Record],
+    separator: &str,
+    escape: fn(&str) -> String,
+) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "{}",
+        ["input", "algorithm", "name", "hex", "distance"].join(separator)
+    )?;
+    for record in records {
+        let fields = [
+            format!("{:X}", record.input),
+            escape(&record.algorithm.to_string()),
+            escape(record.name),
+            format!("{:X}", record.color),
+            record.distance.to_string(),
+        ];
+        writeln!(writer, "{}", fields.join(separator))?;
+    }
+    Ok(())
+}
+
+fn json_string(value: &str) -> String {
+    let mut res = String::with_capacity(value.len() + 2);
+    res.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(res, "\\u{:04x}", u32::from(ch));
+            }
+            ch => res.push(ch),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
@@ -7,6 +7,9 @@ macro_rules! gen_cli_test {
     (ERR $cmd:ident) => {
         $cmd = $cmd.failure()
     };
+    (MISMATCH $cmd:ident) => {
+        $cmd = $cmd.code(2)
+    };
     (eq $exp:expr) => {
         predicates::ord::eq($exp)
     };
@@ -28,26 +31,420 @@ macro_rules! gen_cli_test {
             gen_cli_test!($res cmd);
         }
     };
+    ($name:ident $res:ident $args:expr, stdin $stdin:expr; $($stream:ident $pred:ident $exp:expr),*) => {
+        #[test]
+        fn $name() {
+            let mut cmd = assert_cmd::cargo_bin_cmd!("codi");
+            let mut cmd = cmd.args($args).write_stdin($stdin).assert();
+            $(gen_cli_test!($stream cmd $pred $exp);)*
+            gen_cli_test!($res cmd);
+        }
+    };
     () => {}
 }
 
 const HELP_REGEX: &str = "(?s)Usage.*Args.*Options";
 
 gen_cli_test! {help_msg OK ["--help"]; out regex HELP_REGEX}
+gen_cli_test! {help_distances OK ["--help"]; out regex "(?s)ICtCp distance multiplied by 720, jzazbz in ΔEz multiplied by 450"}
 gen_cli_test! {wrong_usage ERR ["--not-exist-option"]; err regex HELP_REGEX, err regex "invalid option '--not-exist-option'"}
 gen_cli_test! {wrong_hex ERR ["12345"]; err regex "cannot parse argument \"12345\""}
 gen_cli_test! {correct_hex OK ["#FF55FF"]; out eq
-"+--------------------+------------+---------+----+
-| Algorithm          | HTML color | Hex     |    |
-+--------------------+------------+---------+----+
-| > Original color   | unknown    | #FF55FF |    |
-+--------------------+------------+---------+----+
-| Euclidean          | violet     | #EE82EE |    |
-+--------------------+------------+---------+----+
-| Euclidean Improved | violet     | #EE82EE |    |
-+--------------------+------------+---------+----+
-| CIE94              | magenta    | #FF00FF |    |
-+--------------------+------------+---------+----+
+"+--------------------+------------+---------+----+----------+------------+
+| Algorithm          | Color name | Hex     |    | Distance | Difference |
++--------------------+------------+---------+----+----------+------------+
+| > Original color   | unknown    | #FF55FF |    |          |            |
++--------------------+------------+---------+----+----------+------------+
+| Euclidean          | violet     | #EE82EE |    | 51.02    | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| Euclidean Improved | violet     | #EE82EE |    | 108.77   | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CIE76              | magenta    | #FF00FF |    | 18.67    | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CIE94              | magenta    | #FF00FF |    | 6.35     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CIE94 Textiles     | magenta    | #FF00FF |    | 4.16     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CMC(2:1)           | magenta    | #FF00FF |    | 5.73     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CMC(1:1)           | magenta    | #FF00FF |    | 6.85     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| DIN99              | magenta    | #FF00FF |    | 5.46     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| DIN99o             | magenta    | #FF00FF |    | 6.19     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CIEDE2000          | magenta    | #FF00FF |    | 5.51     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| CAM16-UCS          | magenta    | #FF00FF |    | 6.42     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| Oklab              | magenta    | #FF00FF |    | 6.37     | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| ICtCp              | magenta    | #FF00FF |    | 29.02    | noticeable |
++--------------------+------------+---------+----+----------+------------+
+| Jzazbz             | magenta    | #FF00FF |    | 10.34    | noticeable |
++--------------------+------------+---------+----+----------+------------+
 "
 }
 gen_cli_test! {hex_matches_html_color OK ["ff7f50"]; out regex "> Original color.*coral.* #FF7F50"}
+gen_cli_test! {css_color OK ["rgb(255 127 80 / 50%)"]; out regex "> Original color.*coral.* #FF7F50"}
+gen_cli_test! {css_named_color OK ["Coral"]; out regex "> Original color.*coral.* #FF7F50"}
+gen_cli_test! {wrong_css_color ERR ["hsl(10px 0% 0%)"]; err regex "value type is not allowed here at byte 4"}
+
+const PALETTE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/palette.txt");
+const GIMP_PALETTE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/palette.gpl");
+const INVALID_PALETTE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/invalid-palette.txt"
+);
+
+gen_cli_test! {custom_palette OK ["--palette", PALETTE, "#FF55FF"]; out eq
+"+--------------------+-------------+---------+----+----------+------------+
+| Algorithm          | Color name  | Hex     |    | Distance | Difference |
++--------------------+-------------+---------+----+----------+------------+
+| > Original color   | unknown     | #FF55FF |    |          |            |
++--------------------+-------------+---------+----+----------+------------+
+| Euclidean          | brand-white | #FAFAFA |    | 165.15   | different  |
++--------------------+-------------+---------+----+----------+------------+
+| Euclidean Improved | brand-white | #FAFAFA |    | 330.49   | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CIE76              | brand-blue  | #00005F |    | 73.13    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CIE94              | brand-white | #FAFAFA |    | 37.23    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CIE94 Textiles     | brand-white | #FAFAFA |    | 23.65    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CMC(2:1)           | brand-blue  | #00005F |    | 28.23    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CMC(1:1)           | brand-white | #FAFAFA |    | 38.99    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| DIN99              | brand-red   | #DC143C |    | 30.69    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| DIN99o             | brand-red   | #DC143C |    | 44.19    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CIEDE2000          | brand-red   | #DC143C |    | 34.90    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| CAM16-UCS          | brand-red   | #DC143C |    | 36.04    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| Oklab              | brand-red   | #DC143C |    | 27.88    | different  |
++--------------------+-------------+---------+----+----------+------------+
+| ICtCp              | brand-white | #FAFAFA |    | 119.19   | different  |
++--------------------+-------------+---------+----+----------+------------+
+| Jzazbz             | brand-white | #FAFAFA |    | 59.14    | different  |
++--------------------+-------------+---------+----+----------+------------+
+"
+}
+gen_cli_test! {custom_palette_exact OK ["--palette", PALETTE, "00005f"]; out regex "> Original color.*brand-blue.* #00005F"}
+gen_cli_test! {gimp_palette OK ["--palette", GIMP_PALETTE, "00005f"]; out regex "> Original color.*Brand Blue.* #00005F"}
+gen_cli_test! {palette_not_exist ERR ["--palette", "not-exist.txt", "#FF55FF"]; err regex "cannot read palette not-exist.txt"}
+gen_cli_test! {palette_invalid ERR ["--palette", INVALID_PALETTE, "#FF55FF"]; err regex "invalid palette .*: line 2"}
+
+const COLORS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/colors.txt");
+
+gen_cli_test! {batch_positional OK ["#FF55FF", "ff7f50"]; out eq
+"+---------+----+-----------+--------------------+---------+---------+----------------+----------+----------+---------+---------+-----------+-----------+---------+---------+---------+
+| Color   |    | Euclidean | Euclidean Improved | CIE76   | CIE94   | CIE94 Textiles | CMC(2:1) | CMC(1:1) | DIN99   | DIN99o  | CIEDE2000 | CAM16-UCS | Oklab   | ICtCp   | Jzazbz  |
++---------+----+-----------+--------------------+---------+---------+----------------+----------+----------+---------+---------+-----------+-----------+---------+---------+---------+
+| #FF55FF |    | violet    | violet             | magenta | magenta | magenta        | magenta  | magenta  | magenta | magenta | magenta   | magenta   | magenta | magenta | magenta |
++---------+----+-----------+--------------------+---------+---------+----------------+----------+----------+---------+---------+-----------+-----------+---------+---------+---------+
+| #FF7F50 |    | coral     | coral              | coral   | coral   | coral          | coral    | coral    | coral   | coral   | coral     | coral     | coral   | coral   | coral   |
++---------+----+-----------+--------------------+---------+---------+----------------+----------+----------+---------+---------+-----------+-----------+---------+---------+---------+
+"
+}
+gen_cli_test! {batch_stdin OK ["-"], stdin "#FF55FF\n\nff7f50\n"; out regex "(?s)#FF55FF.*violet.*#FF7F50.*coral"}
+gen_cli_test! {batch_stdin_error ERR ["-"], stdin "#FF55FF\n12345\n"; err regex "stdin: line 2: cannot parse \"12345\""}
+gen_cli_test! {batch_input_file OK ["--input", COLORS, "00ff00"]; out regex "(?s)#FF0000.*red.*#00005F.*navy.*#00FF00.*lime"}
+gen_cli_test! {batch_input_not_exist ERR ["--input", "not-exist.txt"]; err regex "not-exist.txt: "}
+
+const ESCAPE_PALETTE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/escape.gpl");
+
+gen_cli_test! {format_json OK ["--format", "json", "--palette", PALETTE, "#FAFAFA"]; out eq
+r##"[
+  {"input": "#FAFAFA", "algorithm": "Euclidean", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "Euclidean Improved", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CIE76", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CIE94", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CIE94 Textiles", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CMC(2:1)", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CMC(1:1)", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "DIN99", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "DIN99o", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CIEDE2000", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "CAM16-UCS", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "Oklab", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "ICtCp", "name": "brand-white", "hex": "#FAFAFA", "distance": 0},
+  {"input": "#FAFAFA", "algorithm": "Jzazbz", "name": "brand-white", "hex": "#FAFAFA", "distance": 0}
+]
+"##
+}
+gen_cli_test! {format_json_escape OK ["--format", "json", "--palette", ESCAPE_PALETTE, "#DC143C"]; out regex r#""name": "Red, \\"dark\\"""#}
+gen_cli_test! {format_csv OK ["--format", "csv", "--palette", PALETTE, "#FAFAFA", "#DC143C"]; out eq
+"input,algorithm,name,hex,distance
+#FAFAFA,Euclidean,brand-white,#FAFAFA,0
+#FAFAFA,Euclidean Improved,brand-white,#FAFAFA,0
+#FAFAFA,CIE76,brand-white,#FAFAFA,0
+#FAFAFA,CIE94,brand-white,#FAFAFA,0
+#FAFAFA,CIE94 Textiles,brand-white,#FAFAFA,0
+#FAFAFA,CMC(2:1),brand-white,#FAFAFA,0
+#FAFAFA,CMC(1:1),brand-white,#FAFAFA,0
+#FAFAFA,DIN99,brand-white,#FAFAFA,0
+#FAFAFA,DIN99o,brand-white,#FAFAFA,0
+#FAFAFA,CIEDE2000,brand-white,#FAFAFA,0
+#FAFAFA,CAM16-UCS,brand-white,#FAFAFA,0
+#FAFAFA,Oklab,brand-white,#FAFAFA,0
+#FAFAFA,ICtCp,brand-white,#FAFAFA,0
+#FAFAFA,Jzazbz,brand-white,#FAFAFA,0
+#DC143C,Euclidean,brand-red,#DC143C,0
+#DC143C,Euclidean Improved,brand-red,#DC143C,0
+#DC143C,CIE76,brand-red,#DC143C,0
+#DC143C,CIE94,brand-red,#DC143C,0
+#DC143C,CIE94 Textiles,brand-red,#DC143C,0
+#DC143C,CMC(2:1),brand-red,#DC143C,0
+#DC143C,CMC(1:1),brand-red,#DC143C,0
+#DC143C,DIN99,brand-red,#DC143C,0
+#DC143C,DIN99o,brand-red,#DC143C,0
+#DC143C,CIEDE2000,brand-red,#DC143C,0
+#DC143C,CAM16-UCS,brand-red,#DC143C,0
+#DC143C,Oklab,brand-red,#DC143C,0
+#DC143C,ICtCp,brand-red,#DC143C,0
+#DC143C,Jzazbz,brand-red,#DC143C,0
+"
+}
+gen_cli_test! {format_csv_escape OK ["--format", "csv", "--palette", ESCAPE_PALETTE, "#DC143C"]; out regex r#"#DC143C,CIE94,"Red, ""dark""",#DC143C,0"#}
+gen_cli_test! {format_tsv OK ["--format", "tsv", "--palette", PALETTE, "#FAFAFA"]; out eq
+"input\talgorithm\tname\thex\tdistance
+#FAFAFA\tEuclidean\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tEuclidean Improved\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCIE76\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCIE94\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCIE94 Textiles\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCMC(2:1)\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCMC(1:1)\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tDIN99\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tDIN99o\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCIEDE2000\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tCAM16-UCS\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tOklab\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tICtCp\tbrand-white\t#FAFAFA\t0
+#FAFAFA\tJzazbz\tbrand-white\t#FAFAFA\t0
+"
+}
+gen_cli_test! {format_table OK ["--format", "table", "ff7f50"]; out regex "> Original color.*coral.* #FF7F50"}
+gen_cli_test! {format_unknown ERR ["--format", "xml", "ff7f50"]; err regex "unknown format \"xml\""}
+
+gen_cli_test! {select_algorithms OK ["--algorithm", "euclidean", "--algorithm", "CIEDE2000", "#FF55FF"]; out eq
+"+------------------+------------+---------+----+----------+------------+
+| Algorithm        | Color name | Hex     |    | Distance | Difference |
++------------------+------------+---------+----+----------+------------+
+| > Original color | unknown    | #FF55FF |    |          |            |
++------------------+------------+---------+----+----------+------------+
+| Euclidean        | violet     | #EE82EE |    | 51.02    | noticeable |
++------------------+------------+---------+----+----------+------------+
+| CIEDE2000        | magenta    | #FF00FF |    | 5.51     | noticeable |
++------------------+------------+---------+----+----------+------------+
+"
+}
+gen_cli_test! {select_algorithm_csv OK ["--format", "csv", "--algorithm", "ciede2000", "#FF55FF", "ff7f50"]; out regex "^input,algorithm,name,hex,distance\n#FF55FF,CIEDE2000,magenta,#FF00FF,[0-9.]+\n#FF7F50,CIEDE2000,coral,#FF7F50,0\n$"}
+gen_cli_test! {unknown_algorithm ERR ["--algorithm", "foo", "#FF55FF"]; err regex "cannot parse argument \"foo\": unknown algorithm"}
+gen_cli_test! {list_algorithms OK ["--list-algorithms"]; out regex "(?s)euclidean .*Euclidean .*euclidean-improved .*Euclidean Improved.*ciede2000 .*CIEDE2000"}
+gen_cli_test! {select_cmc OK ["--algorithm", "CMC-2:1", "--algorithm", "cmc-1:1", "diff", "#FF55FF", "magenta"]; out regex "(?s)CMC\\(2:1\\) .*[|] 5.73 .*CMC\\(1:1\\) .*[|] 6.85 "}
+
+gen_cli_test! {top_n OK ["--top", "3", "--algorithm", "euclidean", "--algorithm", "ciede2000", "#FF55FF"]; out eq
+"+------------------+------------+---------+----+----------+------------+
+| Algorithm        | Color name | Hex     |    | Distance | Difference |
++------------------+------------+---------+----+----------+------------+
+| > Original color | unknown    | #FF55FF |    |          |            |
++------------------+------------+---------+----+----------+------------+
+| Euclidean        | violet     | #EE82EE |    | 51.02    | noticeable |
++------------------+------------+---------+----+----------+------------+
+|                  | orchid     | #DA70D6 |    | 61.47    | noticeable |
++------------------+------------+---------+----+----------+------------+
+|                  | hotpink    | #FF69B4 |    | 77.62    | different  |
++------------------+------------+---------+----+----------+------------+
+| CIEDE2000        | magenta    | #FF00FF |    | 5.51     | noticeable |
++------------------+------------+---------+----+----------+------------+
+|                  | violet     | #EE82EE |    | 7.19     | noticeable |
++------------------+------------+---------+----+----------+------------+
+|                  | orchid     | #DA70D6 |    | 7.41     | noticeable |
++------------------+------------+---------+----+----------+------------+
+"
+}
+gen_cli_test! {top_n_batch OK ["--top", "2", "--algorithm", "ciede2000", "#FF55FF", "#00FF00"]; out regex "#FF55FF .*magenta, violet.*\n.*\n.*#00FF00 .*lime, lawngreen"}
+gen_cli_test! {top_n_csv OK ["--top", "2", "--format", "csv", "--algorithm", "cie94", "#FF55FF"]; out regex "^input,algorithm,name,hex,distance\n#FF55FF,CIE94,magenta,#FF00FF,[0-9.]+\n#FF55FF,CIE94,orchid,#DA70D6,[0-9.]+\n$"}
+gen_cli_test! {top_more_than_palette OK ["--top", "10", "--palette", PALETTE, "--algorithm", "oklab", "--format", "tsv", "#FAFAFA"]; out regex "^input\talgorithm\tname\thex\tdistance\n(#FAFAFA\tOklab\t.*\n){3}$"}
+gen_cli_test! {top_zero ERR ["--top", "0", "#FF55FF"]; err regex "cannot parse argument \"0\""}
+
+gen_cli_test! {within OK ["--within", "10", "--algorithm", "ciede2000", "#FF55FF"]; out eq
+"+------------------+------------+---------+----+----------+------------+
+| Algorithm        | Color name | Hex     |    | Distance | Difference |
++------------------+------------+---------+----+----------+------------+
+| > Original color | unknown    | #FF55FF |    |          |            |
++------------------+------------+---------+----+----------+------------+
+| CIEDE2000        | magenta    | #FF00FF |    | 5.51     | noticeable |
++------------------+------------+---------+----+----------+------------+
+|                  | violet     | #EE82EE |    | 7.19     | noticeable |
++------------------+------------+---------+----+----------+------------+
+|                  | orchid     | #DA70D6 |    | 7.41     | noticeable |
++------------------+------------+---------+----+----------+------------+
+"
+}
+gen_cli_test! {within_none OK ["--within", "1", "--algorithm", "cie94", "#FF55FF"]; out eq
+"+------------------+------------+---------+----+----------+------------+
+| Algorithm        | Color name | Hex     |    | Distance | Difference |
++------------------+------------+---------+----+----------+------------+
+| > Original color | unknown    | #FF55FF |    |          |            |
++------------------+------------+---------+----+----------+------------+
+| CIE94            | -          |         |    |          |            |
++------------------+------------+---------+----+----------+------------+
+"
+}
+gen_cli_test! {within_top OK ["--within", "10", "--top", "2", "--algorithm", "ciede2000", "--format", "csv", "#FF55FF"]; out regex "^input,algorithm,name,hex,distance\n#FF55FF,CIEDE2000,magenta,#FF00FF,[0-9.]+\n#FF55FF,CIEDE2000,violet,#EE82EE,[0-9.]+\n$"}
+gen_cli_test! {within_batch OK ["--within", "3", "#FF55FF", "ff7f50"]; out regex "#FF55FF .*[|] - .*\n.*\n.*#FF7F50 .*[|] coral "}
+gen_cli_test! {within_negative ERR ["--within", "-1", "#FF55FF"]; err regex "cannot parse argument \"-1\": expected non-negative distance"}
+
+gen_cli_test! {diff OK ["diff", "#FF55FF", "magenta"]; out eq
+"+--------------------+----------+------------+
+| Algorithm          | Distance | Difference |
++--------------------+----------+------------+
+| > Colors           | #FF55FF       #FF00FF |
++--------------------+----------+------------+
+| Euclidean          | 85.00    | noticeable |
++--------------------+----------+------------+
+| Euclidean Improved | 170.00   | noticeable |
++--------------------+----------+------------+
+| CIE76              | 18.67    | noticeable |
++--------------------+----------+------------+
+| CIE94              | 6.35     | noticeable |
++--------------------+----------+------------+
+| CIE94 Textiles     | 4.16     | noticeable |
++--------------------+----------+------------+
+| CMC(2:1)           | 5.73     | noticeable |
++--------------------+----------+------------+
+| CMC(1:1)           | 6.85     | noticeable |
++--------------------+----------+------------+
+| DIN99              | 5.46     | noticeable |
++--------------------+----------+------------+
+| DIN99o             | 6.19     | noticeable |
++--------------------+----------+------------+
+| CIEDE2000          | 5.51     | noticeable |
++--------------------+----------+------------+
+| CAM16-UCS          | 6.42     | noticeable |
++--------------------+----------+------------+
+| Oklab              | 6.37     | noticeable |
++--------------------+----------+------------+
+| ICtCp              | 29.02    | noticeable |
++--------------------+----------+------------+
+| Jzazbz             | 10.34    | noticeable |
++--------------------+----------+------------+
+"
+}
+gen_cli_test! {diff_max_pass OK ["--max", "6", "--algorithm", "ciede2000", "diff", "#FF55FF", "magenta"]; out regex "CIEDE2000 .*[|] 5.51 .*[|] noticeable .*[|] pass "}
+gen_cli_test! {diff_max_fail MISMATCH ["--max", "6", "--algorithm", "cie94", "--algorithm", "ciede2000", "diff", "#FF55FF", "magenta"]; out eq
+"+-----------+----------+------------+--------+
+| Algorithm | Distance | Difference | Result |
++-----------+----------+------------+--------+
+| > Colors  | #FF55FF       #FF00FF          |
++-----------+----------+------------+--------+
+| CIE94     | 6.35     | noticeable | fail   |
++-----------+----------+------------+--------+
+| CIEDE2000 | 5.51     | noticeable | pass   |
++-----------+----------+------------+--------+
+"
+}
+gen_cli_test! {diff_equal OK ["--max", "0", "diff", "coral", "rgb(255 127 80)"]; out regex "(?s)CIEDE2000 .*[|] 0.00 .*[|] imperceptible .*[|] pass "}
+gen_cli_test! {diff_max_default_ciede2000 OK ["--max", "2.3", "diff", "#808080", "#818181"]; out eq
+"+--------------------+----------+---------------+--------+
+| Algorithm          | Distance | Difference    | Result |
++--------------------+----------+---------------+--------+
+| > Colors           | #808080       #818181             |
++--------------------+----------+---------------+--------+
+| Euclidean          | 1.73     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| Euclidean Improved | 4.06     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| CIE76              | 0.39     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| CIE94              | 0.39     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| CIE94 Textiles     | 0.20     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| CMC(2:1)           | 0.17     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| CMC(1:1)           | 0.35     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| DIN99              | 0.35     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| DIN99o             | 0.38     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| CIEDE2000          | 0.38     | imperceptible | pass   |
++--------------------+----------+---------------+--------+
+| CAM16-UCS          | 0.40     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| Oklab              | 0.34     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| ICtCp              | 1.14     | imperceptible |        |
++--------------------+----------+---------------+--------+
+| Jzazbz             | 0.39     | imperceptible |        |
++--------------------+----------+---------------+--------+
+"
+}
+gen_cli_test! {diff_max_all_rows MISMATCH ["--max", "6", "diff", "#808080", "#FFFFFF"]; out regex "(?s)Euclidean +[|] 219.97 +[|] different +[|] +[|].*CIEDE2000 .*[|] fail .*Jzazbz +[|] [0-9.]+ +[|] different +[|] +[|]"}
+gen_cli_test! {diff_format ERR ["--format", "json", "diff", "#FF55FF", "magenta"]; err regex "--format cannot be used with diff, only with color search"}
+gen_cli_test! {max_without_diff ERR ["--max", "1", "#FF55FF"]; err regex "--max cannot be used with color search, only with diff"}
+gen_cli_test! {diff_one_color ERR ["diff", "#FF55FF"]; err regex "diff expects exactly two colors"}
+gen_cli_test! {diff_not_first ERR ["#FF55FF", "diff"]; err regex "cannot parse argument \"diff\""}
+
+gen_cli_test! {convert OK ["convert", "coral"]; out eq
+"+--------------+-----------------------+
+| hex          | #FF7F50               |
++--------------+-----------------------+
+| rgb          | 255 127 80            |
++--------------+-----------------------+
+| hsl          | 16.114 100.000 65.686 |
++--------------+-----------------------+
+| hsv          | 16.114 68.627 100.000 |
++--------------+-----------------------+
+| hwb          | 16.114 31.373 0.000   |
++--------------+-----------------------+
+| xyz          | 0.503 0.370 0.121     |
++--------------+-----------------------+
+| lab          | 67.296 45.354 47.494  |
++--------------+-----------------------+
+| oklab        | 0.735 0.128 0.109     |
++--------------+-----------------------+
+| oklch        | 0.735 0.168 40.247    |
++--------------+-----------------------+
+| display-p3   | 0.936 0.525 0.358     |
++--------------+-----------------------+
+| rec2020      | 0.837 0.506 0.303     |
++--------------+-----------------------+
+| a98-rgb      | 0.891 0.494 0.327     |
++--------------+-----------------------+
+| prophoto-rgb | 0.760 0.499 0.295     |
++--------------+-----------------------+
+"
+}
+gen_cli_test! {convert_to OK ["--to", "hsl", "--to", "OKLCH", "--precision", "1", "convert", "hsl(200 30% 40%)"]; out eq
+"+-------+-----------------+
+| hsl   | 200.0 30.0 40.0 |
++-------+-----------------+
+| oklch | 0.5 0.1 230.9   |
++-------+-----------------+
+"
+}
+gen_cli_test! {convert_precision OK ["--to", "lab", "--illuminant", "d50", "--precision", "2", "convert", "lab(50 20 30)"]; out regex "[|] lab [|] 50.00 20.00 30.00 [|]"}
+gen_cli_test! {convert_precision_wide_gamut OK ["--to", "display-p3", "--precision", "4", "convert", "color(display-p3 0.5 0.5 0.5)"]; out regex "[|] display-p3 [|] 0.5000 0.5000 0.5000 [|]"}
+gen_cli_test! {convert_gray_hsl OK ["--to", "hsl", "--precision", "0", "convert", "gray"]; out regex "[|] hsl [|] 0 0 50 [|]"}
+gen_cli_test! {convert_batch OK ["--to", "hex", "--to", "rgb", "convert", "coral", "-"], stdin "hsl(0 100% 50%)\n"; out regex "(?s)[|] Color .*[|] hex .*[|] rgb .*#FF7F50 .*[|] 255 127 80 .*#FF0000 .*[|] 255 0 0 "}
+gen_cli_test! {convert_format ERR ["--format", "csv", "convert", "coral"]; err regex "--format cannot be used with convert, only with color search"}
+gen_cli_test! {convert_algorithm ERR ["--algorithm", "oklab", "convert", "coral"]; err regex "--algorithm cannot be used with convert, only with color search and diff"}
+gen_cli_test! {convert_unknown_space ERR ["--to", "cmyk", "convert", "coral"]; err regex "unknown color space \"cmyk\""}
+gen_cli_test! {convert_illuminant OK ["--to", "lab", "--illuminant", "d50", "--precision", "1", "convert", "red"]; out regex "[|] lab [|] 54.3 80.8 69.9 [|]"}
+gen_cli_test! {convert_wide_gamut OK ["--to", "display-p3", "--to", "rec2020", "convert", "red"]; out regex "(?s)[|] display-p3 [|] 0.918 0.200 0.139 [|].*[|] rec2020 +[|] 0.792 0.231 0.074 [|]"}
+gen_cli_test! {display_p3_color OK ["--algorithm", "ciede2000", "color(display-p3 0.3 0.6 0.4)"]; out regex "(?s)#259B61 .*[|] seagreen "}
+gen_cli_test! {convert_unknown_illuminant ERR ["--illuminant", "D60", "convert", "red"]; err regex "cannot parse argument \"D60\": unknown illuminant"}
+
+gen_cli_test! {gamut_css OK ["--to", "hex", "convert", "oklch(70% 0.3 150)"]; out regex "[|] hex [|] #00C248 [|]", err regex "warning: \"oklch\\(70% 0.3 150\\)\" is outside of sRGB gamut, mapped to #00C248 with css"}
+gen_cli_test! {gamut_clip OK ["--gamut", "clip", "--to", "hex", "convert", "oklch(70% 0.3 150)"]; out regex "[|] hex [|] #00CB00 [|]", err regex "mapped to #00CB00 with clip"}
+gen_cli_test! {gamut_stdin OK ["--to", "hex", "convert", "-"], stdin "lab(50 -120 30)\n"; out regex "#00855A", err regex "warning: \"lab\\(50 -120 30\\)\" is outside of sRGB gamut"}
+gen_cli_test! {gamut_in_gamut OK ["--to", "hex", "convert", "color(display-p3 0.3 0.6 0.4)"]; err eq ""}
+gen_cli_test! {gamut_unknown ERR ["--gamut", "perceptual", "red"]; err regex "cannot parse argument \"perceptual\": unknown gamut mapping"}
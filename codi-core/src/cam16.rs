@@ -0,0 +1,545 @@
+use libm::{cosf, expf, hypotf, log1pf, powf, sinf, sqrtf};
+use ordered_float::NotNan;
+
+use crate::color_space::{Error, Rgb, Xyz, finite, fmt_components};
+use crate::illuminant::Illuminant;
+use crate::math_utils::{hue_angle, matrix_mul};
+
+/// Matrix to convert XYZ into sharpened cone responses.
+const M16: [[f32; 3]; 3] = [
+    [0.401_288, 0.650_173, -0.051_461],
+    [-0.250_268, 1.204_414, 0.045_854],
+    [-0.002_079, 0.048_952, 0.953_127],
+];
+
+/**
+    Relative luminance of the surround of the viewing field.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Surround {
+    /// Viewing surface colors, e.g. a print in an office.
+    #[default]
+    Average,
+    /// Viewing a display or television in a dim room.
+    Dim,
+    /// Viewing a projector in a dark room.
+    Dark,
+}
+
+impl Surround {
+    pub const ALL: [Self; 3] = [Self::Average, Self::Dim, Self::Dark];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Average => "average",
+            Self::Dim => "dim",
+            Self::Dark => "dark",
+        }
+    }
+
+    /**
+        Factor of the degree of adaptation `F`, impact of surround `c` and
+        chromatic induction factor `Nc`.
+    */
+    const fn factors(self) -> [f32; 3] {
+        match self {
+            Self::Average => [1.0, 0.69, 1.0],
+            Self::Dim => [0.9, 0.59, 0.9],
+            Self::Dark => [0.8, 0.525, 0.8],
+        }
+    }
+}
+
+impl core::fmt::Display for Surround {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/**
+    Viewing conditions of the CAM16 color appearance model. The same color
+    looks different depending on the light and the surrounding of it.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewingConditions {
+    white: Illuminant,
+    adapting_luminance: f32,
+    background_luminance: f32,
+    surround: Surround,
+    discounting: bool,
+}
+
+/**
+    Parameters of the model which depend only on the viewing conditions.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Environment {
+    /// Scale of the cone responses for the adaptation to the white.
+    d_rgb: [f32; 3],
+    /// Luminance level adaptation factor.
+    f_l: f32,
+    /// Background induction factor.
+    n: f32,
+    c: f32,
+    n_c: f32,
+    n_bb: f32,
+    z: f32,
+    /// Achromatic response to the white.
+    a_w: f32,
+}
+
+impl ViewingConditions {
+    /**
+        Conditions of the sRGB reference viewing environment: D65 white, 64
+        lux ambient illumination and 20% gray background.
+    */
+    pub const SRGB: Self = Self::new(
+        Illuminant::D65,
+        64.0 / core::f32::consts::PI * 0.2,
+        20.0,
+        Surround::Average,
+        false,
+    );
+
+    /**
+        Creates viewing conditions, colors are relative to the `white` of the
+        light source.
+
+        `adapting_luminance` is the luminance of the adapting field `L_A` in
+        cd/m², usually 20% of the luminance of the white.
+        `background_luminance` is the relative luminance of the background
+        `Y_b`, `100` is the luminance of the white. `discounting` tells
+        whether the observer fully adapts to the white, e.g. for the surface
+        colors under the known light source.
+
+        # Panics
+
+        Panics if any of the luminances is not positive or not finite.
+    */
+    pub const fn new(
+        white: Illuminant,
+        adapting_luminance: f32,
+        background_luminance: f32,
+        surround: Surround,
+        discounting: bool,
+    ) -> Self {
+        assert!(
+            adapting_luminance.is_finite()
+                && adapting_luminance > 0.0
+                && background_luminance.is_finite()
+                && background_luminance > 0.0,
+            "CAM16 luminances must be positive and finite"
+        );
+        Self {
+            white,
+            adapting_luminance,
+            background_luminance,
+            surround,
+            discounting,
+        }
+    }
+
+    /**
+        Same conditions in the given surround.
+    */
+    #[must_use]
+    pub const fn with_surround(self, surround: Surround) -> Self {
+        Self { surround, ..self }
+    }
+
+    pub const fn white(&self) -> Illuminant {
+        self.white
+    }
+
+    /// Luminance of the adapting field `L_A` in cd/m².
+    pub const fn adapting_luminance(&self) -> f32 {
+        self.adapting_luminance
+    }
+
+    /// Relative luminance of the background `Y_b`.
+    pub const fn background_luminance(&self) -> f32 {
+        self.background_luminance
+    }
+
+    pub const fn surround(&self) -> Surround {
+        self.surround
+    }
+
+    pub const fn discounting(&self) -> bool {
+        self.discounting
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    pub(crate) fn environment(&self) -> Environment {
+        let [f, c, n_c] = self.surround.factors();
+        let l_a = self.adapting_luminance;
+        let white = self.white.white_point();
+        let rgb_w = cone_response(white);
+
+        let d = if self.discounting {
+            1.0
+        } else {
+            (f * (1.0 - expf((-l_a - 42.0) / 92.0) / 3.6)).clamp(0.0, 1.0)
+        };
+        let d_rgb = rgb_w.map(|col| d / col + 1.0 - d);
+
+        let k = 1.0 / (5.0 * l_a + 1.0);
+        let k4 = powf(k, 4.0);
+        let f_l = 0.2 * k4 * (5.0 * l_a) + 0.1 * powf(1.0 - k4, 2.0) * powf(5.0 * l_a, 1.0 / 3.0);
+
+        let n = self.background_luminance / 100.0;
+        let n_bb = 0.725 * powf(n, -0.2);
+        let z = 1.48 + sqrtf(n);
+
+        let mut env = Environment {
+            d_rgb,
+            f_l,
+            n,
+            c,
+            n_c,
+            n_bb,
+            z,
+            a_w: 0.0,
+        };
+        env.a_w = env.achromatic(env.adapt(rgb_w));
+        env
+    }
+}
+
+impl Default for ViewingConditions {
+    fn default() -> Self {
+        Self::SRGB
+    }
+}
+
+impl Environment {
+    /**
+        Environment of [`ViewingConditions::SRGB`], computed ahead so that
+        distances can be constant.
+    */
+    pub(crate) const SRGB: Self = Self {
+        d_rgb: [1.020_834_8, 0.986_528_75, 0.935_003_64],
+        f_l: 0.273_130_57,
+        n: 0.2,
+        c: 0.69,
+        n_c: 1.0,
+        n_bb: 1.000_304,
+        z: 1.927_213_7,
+        a_w: 25.518_509,
+    };
+
+    /**
+        Adapted and compressed cone responses.
+    */
+    fn adapt(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let mut res = rgb;
+        for (col, scale) in res.iter_mut().zip(self.d_rgb) {
+            let val = powf(self.f_l * (*col * scale).abs(), 0.42);
+            *col = (400.0 * val / (val + 27.13)).copysign(*col) + 0.1;
+        }
+        res
+    }
+
+    fn achromatic(&self, [r, g, b]: [f32; 3]) -> f32 {
+        (2.0 * r + g + 0.05 * b - 0.305) * self.n_bb
+    }
+}
+
+fn cone_response(xyz: Xyz) -> [f32; 3] {
+    let res = matrix_mul(&M16, &[[xyz.x()], [xyz.y()], [xyz.z()]]);
+    [res[0][0], res[1][0], res[2][0]]
+}
+
+/**
+    Appearance correlates of the color in the CAM16 color appearance model.
+
+    Paper: Li et al., Comprehensive color solutions: CAM16, CAT16, and
+    CAM16-UCS, 2017.
+
+    # Example
+
+    ```
+    use codi_core::cam16::{Cam16, Surround, ViewingConditions};
+    use codi_core::color_space::rgb;
+
+    let color = rgb(255, 127, 80);
+    let dark = ViewingConditions::SRGB.with_surround(Surround::Dark);
+    let (office, cinema) = (Cam16::from(color), Cam16::from_xyz(color.into(), &dark));
+    assert!(cinema.j() > office.j());
+    assert!(cinema.c() < office.c());
+    ```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cam16 {
+    pub(crate) j: NotNan<f32>,
+    pub(crate) c: NotNan<f32>,
+    pub(crate) h: NotNan<f32>,
+    pub(crate) m: NotNan<f32>,
+    pub(crate) s: NotNan<f32>,
+    pub(crate) q: NotNan<f32>,
+}
+
+impl Cam16 {
+    /**
+        Finds appearance of the color with the tristimulus values relative
+        to the white of the viewing conditions.
+    */
+    pub fn from_xyz(xyz: Xyz, conditions: &ViewingConditions) -> Self {
+        Self::from_xyz_in(xyz, &conditions.environment())
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    pub(crate) fn from_xyz_in(xyz: Xyz, env: &Environment) -> Self {
+        let [r, g, b] = env.adapt(cone_response(xyz));
+
+        let a = r - 12.0 * g / 11.0 + b / 11.0;
+        let b_ = (r + g - 2.0 * b) / 9.0;
+        let h = hue_angle(a, b_);
+        let e_t = (cosf(h.to_radians() + 2.0) + 3.8) / 4.0;
+
+        // Rounding errors can make the response of black slightly negative.
+        let j = 100.0 * powf(env.achromatic([r, g, b]).max(0.0) / env.a_w, env.c * env.z);
+        let q = 4.0 / env.c * sqrtf(j / 100.0) * (env.a_w + 4.0) * powf(env.f_l, 0.25);
+        let t =
+            50_000.0 / 13.0 * env.n_c * env.n_bb * e_t * hypotf(a, b_) / (r + g + 21.0 / 20.0 * b);
+        let c = powf(t, 0.9) * sqrtf(j / 100.0) * powf(1.64 - powf(0.29, env.n), 0.73);
+        let m = c * powf(env.f_l, 0.25);
+        // Black has no brightness, so it has no saturation either.
+        let s = if q > 0.0 { 100.0 * sqrtf(m / q) } else { 0.0 };
+
+        unsafe {
+            Self {
+                j: NotNan::new_unchecked(j),
+                c: NotNan::new_unchecked(c),
+                h: NotNan::new_unchecked(h),
+                m: NotNan::new_unchecked(m),
+                s: NotNan::new_unchecked(s),
+                q: NotNan::new_unchecked(q),
+            }
+        }
+    }
+
+    /// Lightness `J`.
+    pub fn j(&self) -> f32 {
+        *self.j
+    }
+
+    /// Chroma `C`.
+    pub fn c(&self) -> f32 {
+        *self.c
+    }
+
+    /// Hue angle `h` in degrees.
+    pub fn h(&self) -> f32 {
+        *self.h
+    }
+
+    /// Colorfulness `M`.
+    pub fn m(&self) -> f32 {
+        *self.m
+    }
+
+    /// Saturation `s`.
+    pub fn s(&self) -> f32 {
+        *self.s
+    }
+
+    /// Brightness `Q`.
+    pub fn q(&self) -> f32 {
+        *self.q
+    }
+}
+
+impl From<Xyz> for Cam16 {
+    fn from(value: Xyz) -> Self {
+        Self::from_xyz(value, &ViewingConditions::SRGB)
+    }
+}
+
+impl From<Rgb> for Cam16 {
+    fn from(value: Rgb) -> Self {
+        Xyz::from(value).into()
+    }
+}
+
+impl core::fmt::Display for Cam16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Cam16", [self.j, self.c, self.h])
+    }
+}
+
+/**
+    CAM16-UCS uniform color space, Euclidean distance in it is a good
+    predictor of the perceived color difference.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cam16Ucs {
+    pub(crate) j: NotNan<f32>,
+    pub(crate) a: NotNan<f32>,
+    pub(crate) b: NotNan<f32>,
+}
+
+impl Cam16Ucs {
+    /**
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(j: f32, a: f32, b: f32) -> Result<Self, Error> {
+        Ok(Self {
+            j: finite(j)?,
+            a: finite(a)?,
+            b: finite(b)?,
+        })
+    }
+
+    pub fn j(&self) -> f32 {
+        *self.j
+    }
+
+    pub fn a(&self) -> f32 {
+        *self.a
+    }
+
+    pub fn b(&self) -> f32 {
+        *self.b
+    }
+}
+
+impl From<Cam16> for Cam16Ucs {
+    fn from(value: Cam16) -> Self {
+        const C1: f32 = 0.007;
+        const C2: f32 = 0.0228;
+
+        let j = 1.7 * *value.j / (1.0 + C1 * *value.j);
+        let m = log1pf(C2 * *value.m) / C2;
+        let h = value.h().to_radians();
+        unsafe {
+            Self {
+                j: NotNan::new_unchecked(j),
+                a: NotNan::new_unchecked(m * cosf(h)),
+                b: NotNan::new_unchecked(m * sinf(h)),
+            }
+        }
+    }
+}
+
+impl From<Rgb> for Cam16Ucs {
+    fn from(value: Rgb) -> Self {
+        Cam16::from(value).into()
+    }
+}
+
+impl TryFrom<[f32; 3]> for Cam16Ucs {
+    type Error = Error;
+
+    fn try_from([j, a, b]: [f32; 3]) -> Result<Self, Error> {
+        Self::new(j, a, b)
+    }
+}
+
+impl core::fmt::Display for Cam16Ucs {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Cam16Ucs", [self.j, self.a, self.b])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::rgb;
+
+    #[test]
+    fn cam16_reference_values() {
+        // Sample from colour-science, values are recomputed for the D65 white
+        // point used here.
+        let conditions =
+            ViewingConditions::new(Illuminant::D65, 318.31, 20.0, Surround::Average, false);
+        let xyz = Xyz::new(0.1901, 0.2, 0.2178).unwrap();
+        let cam = Cam16::from_xyz(xyz, &conditions);
+        let expected = [41.731, 0.098_96, 216.73, 0.102_87, 2.2946, 195.37];
+        let actual = [cam.j(), cam.c(), cam.h(), cam.m(), cam.s(), cam.q()];
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < expected * 1e-3,
+                "got: {actual}, expected: {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn white_and_black() {
+        for surround in Surround::ALL {
+            // White is achromatic only when observer fully adapts to it.
+            let srgb = ViewingConditions::SRGB;
+            let conditions = ViewingConditions::new(
+                srgb.white(),
+                srgb.adapting_luminance(),
+                srgb.background_luminance(),
+                surround,
+                true,
+            );
+            let white = Cam16::from_xyz(Illuminant::D65.white_point(), &conditions);
+            assert!((white.j() - 100.0).abs() < 1e-3, "{surround}: {white}");
+            assert!(white.c() < 1e-2, "{surround}: {white}");
+        }
+
+        let black = Cam16Ucs::from(rgb(0, 0, 0));
+        assert!(black.j().abs() < 1e-3 && black.a().abs() < 1e-3 && black.b().abs() < 1e-3);
+    }
+
+    #[test]
+    fn black_and_near_black() {
+        for color in [rgb(0, 0, 0), rgb(1, 0, 0), rgb(0, 0, 1), rgb(1, 1, 1)] {
+            let cam = Cam16::from(color);
+            let values = [cam.j(), cam.c(), cam.h(), cam.m(), cam.s(), cam.q()];
+            assert!(
+                values.iter().all(|val| val.is_finite()),
+                "{color}: {values:?}"
+            );
+            assert_eq!(cam, Cam16::from(color));
+        }
+
+        let black = Cam16::from(rgb(0, 0, 0));
+        assert!(black.j().abs() < 1e-6 && black.q().abs() < 1e-6 && black.s().abs() < 1e-6);
+    }
+
+    #[test]
+    fn srgb_environment() {
+        let to_array = |env: Environment| {
+            let [r, g, b] = env.d_rgb;
+            [
+                r, g, b, env.f_l, env.n, env.c, env.n_c, env.n_bb, env.z, env.a_w,
+            ]
+        };
+        let computed = to_array(ViewingConditions::SRGB.environment());
+        for (computed, constant) in computed.into_iter().zip(to_array(Environment::SRGB)) {
+            assert!(
+                (computed - constant).abs() < constant * 1e-6,
+                "{computed} != {constant}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "CAM16 luminances must be positive and finite")]
+    fn zero_background_luminance() {
+        let _ = ViewingConditions::new(Illuminant::D65, 64.0, 0.0, Surround::Average, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "CAM16 luminances must be positive and finite")]
+    fn infinite_adapting_luminance() {
+        let _ = ViewingConditions::new(Illuminant::D65, f32::INFINITY, 20.0, Surround::Dim, false);
+    }
+
+    #[test]
+    fn ucs_compresses_colorfulness() {
+        let cam = Cam16::from(rgb(255, 0, 0));
+        let ucs = Cam16Ucs::from(cam);
+        assert!(hypotf(ucs.a(), ucs.b()) < cam.m());
+        assert!((hue_angle(ucs.a(), ucs.b()) - cam.h()).abs() < 1e-3);
+        assert_eq!(Cam16Ucs::new(f32::NAN, 0.0, 0.0), Err(Error::NotANumber));
+    }
+}
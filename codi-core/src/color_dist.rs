@@ -1,13 +1,27 @@
-use libm::{hypotf, powf, sqrtf};
+use libm::{cosf, expf, hypotf, powf, sinf, sqrtf};
 use ordered_float::NotNan;
 
-use crate::color_space::{Cielab, Rgb};
+use crate::cam16::{Cam16, Cam16Ucs, Environment, ViewingConditions};
+use crate::color_space::{Cielab, Din99, Din99o, Ictcp, Jzazbz, Oklab, Rgb};
+use crate::math_utils::hue_angle;
 
 pub trait ColorDistance: core::fmt::Display {
     /**
-        Find distance between two colors.
+        Find distance between two colors in units of the algorithm, e.g. ΔE
+        for the CIE formulas. It is `0` for equal colors, just noticeable
+        difference depends on the formula, e.g. it is about `1.0` for
+        CIEDE2000 and `2.3` for CIE76.
     */
-    fn dist(&self, c1: Rgb, c2: Rgb) -> NotNan<f32>;
+    fn dist(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        let dist = sqrtf(*self.dist_squared(c1, c2));
+        NotNan::new(dist).unwrap()
+    }
+
+    /**
+        Find squared [`ColorDistance::dist`]. It is cheaper to compute and
+        preserves the order, so it is used for ranking the candidates.
+    */
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32>;
 
     /**
         Find closest value to the `target`.
@@ -19,18 +33,188 @@ pub trait ColorDistance: core::fmt::Display {
         candidates
             .iter()
             .enumerate()
-            .min_by_key(|(_idx, other)| self.dist(target, **other))
+            .min_by_key(|(_idx, other)| self.dist_squared(target, **other))
             .map(|(idx, _)| idx)
     }
+
+    /**
+        Find `k` closest values to the `target`.
+
+        # Returns
+        Indices of the closest values from candidates with their distances
+        sorted by distance in ascending order. Values with equal distance are
+        sorted by index. Length is `k` or less if there are not enough
+        candidates.
+    */
+    #[cfg(feature = "alloc")]
+    fn find_k_closest(
+        &self,
+        target: Rgb,
+        candidates: &[Rgb],
+        k: usize,
+    ) -> alloc::vec::Vec<(usize, NotNan<f32>)> {
+        let mut dists: alloc::vec::Vec<_> = candidates
+            .iter()
+            .enumerate()
+            .map(|(idx, other)| (idx, self.dist_squared(target, *other)))
+            .collect();
+        let key = |&(idx, dist): &(usize, NotNan<f32>)| (dist, idx);
+
+        if k < dists.len() {
+            dists.select_nth_unstable_by_key(k, key);
+            dists.truncate(k);
+        }
+        dists.sort_unstable_by_key(key);
+        for (_, dist) in &mut dists {
+            *dist = NotNan::new(sqrtf(**dist)).unwrap();
+        }
+        dists
+    }
+
+    /**
+        Find all values within `max_distance` from the `target`, inclusive.
+
+        # Returns
+        Indices of the found values with their distances sorted by distance
+        in ascending order. Values with equal distance are sorted by index.
+    */
+    #[cfg(feature = "alloc")]
+    fn find_within(
+        &self,
+        target: Rgb,
+        candidates: &[Rgb],
+        max_distance: f32,
+    ) -> alloc::vec::Vec<(usize, NotNan<f32>)> {
+        let mut dists: alloc::vec::Vec<_> = candidates
+            .iter()
+            .enumerate()
+            .map(|(idx, other)| (idx, self.dist(target, *other)))
+            .filter(|(_, dist)| **dist <= max_distance)
+            .collect();
+        dists.sort_unstable_by_key(|&(idx, dist)| (dist, idx));
+        dists
+    }
+}
+
+/**
+    All algorithms with their names for lookup, see [`find_algorithm`].
+*/
+pub const REGISTRY: [(&str, &'static dyn ColorDistance); 14] = [
+    ("euclidean", &Euclidean),
+    ("euclidean-improved", &EuclideanImproved),
+    ("cie76", &CIE76),
+    ("cie94", &CIE94::GRAPHIC_ARTS),
+    ("cie94-textiles", &CIE94::TEXTILES),
+    ("cmc-2:1", &Cmc::ACCEPTABILITY),
+    ("cmc-1:1", &Cmc::PERCEPTIBILITY),
+    ("din99", &Din99Distance),
+    ("din99o", &Din99oDistance),
+    ("ciede2000", &CIEDE2000::new(1.0, 1.0, 1.0)),
+    ("cam16-ucs", &Cam16UcsDistance::SRGB),
+    ("oklab", &OklabDistance),
+    ("ictcp", &ItpDistance),
+    ("jzazbz", &JzazbzDistance),
+];
+
+pub const ALGORITHMS: [&'static dyn ColorDistance; REGISTRY.len()] = __registry_algorithms();
+
+const fn __registry_algorithms<const N: usize>() -> [&'static dyn ColorDistance; N] {
+    let mut algorithms: [&'static dyn ColorDistance; N] = [&Euclidean; N];
+    let mut idx = 0;
+    while idx < N {
+        algorithms[idx] = REGISTRY[idx].1;
+        idx += 1;
+    }
+    algorithms
+}
+
+/**
+    Find algorithm by its name from [`REGISTRY`], case-insensitive.
+
+    # Example
+
+    ```
+    use codi_core::color_dist::find_algorithm;
+    assert_eq!(find_algorithm("CIEDE2000").unwrap().to_string(), "CIEDE2000");
+    assert!(find_algorithm("unknown").is_none());
+    ```
+*/
+pub fn find_algorithm(name: &str) -> Option<&'static dyn ColorDistance> {
+    REGISTRY
+        .iter()
+        .find(|(other, _)| other.eq_ignore_ascii_case(name))
+        .map(|(_, alg)| *alg)
+}
+
+/**
+    Color found by the search with its distance to the target color.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<T> {
+    pub color: T,
+    /// Distance in units of the algorithm used for the search.
+    pub distance: NotNan<f32>,
+}
+
+/**
+    How different two colors look to the human eye.
+
+    Thresholds are for the CIEDE2000 ΔE: differences below 1.0 are not
+    perceptible, below 2.0 are perceptible only through close observation
+    and below 10.0 are perceptible at a glance.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Perception {
+    Imperceptible,
+    BarelyNoticeable,
+    Noticeable,
+    Different,
+}
+
+impl Perception {
+    pub fn from_delta_e(delta_e: f32) -> Self {
+        match delta_e {
+            ..1.0 => Self::Imperceptible,
+            ..2.0 => Self::BarelyNoticeable,
+            ..10.0 => Self::Noticeable,
+            _ => Self::Different,
+        }
+    }
+
+    /**
+        Perceived difference between two colors using CIEDE2000 ΔE.
+
+        # Example
+
+        ```
+        use codi_core::color_dist::Perception;
+        use codi_core::color_space::rgb;
+        assert_eq!(Perception::between(rgb(0, 0, 0), rgb(1, 1, 1)), Perception::Imperceptible);
+        assert_eq!(Perception::between(rgb(0, 0, 0), rgb(255, 0, 0)), Perception::Different);
+        ```
+    */
+    pub fn between(c1: Rgb, c2: Rgb) -> Self {
+        Self::from_delta_e(*CIEDE2000::default().dist(c1, c2))
+    }
 }
 
-pub const ALGORITHMS: [&'static dyn ColorDistance; 3] = [&Euclidean, &EuclideanImproved, &CIE94];
+impl core::fmt::Display for Perception {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let label = match self {
+            Self::Imperceptible => "imperceptible",
+            Self::BarelyNoticeable => "barely noticeable",
+            Self::Noticeable => "noticeable",
+            Self::Different => "different",
+        };
+        write!(f, "{label}")
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct Euclidean;
 
 impl ColorDistance for Euclidean {
-    fn dist(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
         let dist = powf(f32::from(c1.r) - f32::from(c2.r), 2.0)
             + powf(f32::from(c1.g) - f32::from(c2.g), 2.0)
             + powf(f32::from(c1.b) - f32::from(c2.b), 2.0);
@@ -49,7 +233,7 @@ impl core::fmt::Display for Euclidean {
 pub struct EuclideanImproved;
 
 impl ColorDistance for EuclideanImproved {
-    fn dist(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
         let red_mean = f32::midpoint(f32::from(c1.r), f32::from(c2.r));
         let (d_r, d_g, d_b) = (
             (f32::from(c1.r) - f32::from(c2.r)),
@@ -69,61 +253,1008 @@ impl core::fmt::Display for EuclideanImproved {
     }
 }
 
+/**
+    CIE76 color difference, Euclidean distance in the [`Cielab`] color space.
+*/
 #[derive(Debug, Clone, Copy)]
-pub struct CIE94;
+pub struct CIE76;
 
-impl ColorDistance for CIE94 {
-    #[allow(non_upper_case_globals, non_snake_case)]
-    fn dist(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+impl ColorDistance for CIE76 {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
         let (lab1, lab2) = (Cielab::from(c1), Cielab::from(c2));
+        let dist = powf(*lab1.l - *lab2.l, 2.0)
+            + powf(*lab1.a - *lab2.a, 2.0)
+            + powf(*lab1.b - *lab2.b, 2.0);
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for CIE76 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CIE76")
+    }
+}
 
-        let (kL, K1, K2) = (1.0, 0.045, 0.015);
-        let (kC, kH) = (1.0, 1.0);
+/**
+    CIE94 color difference.
 
-        let (delta_l, delta_a, delta_b) = (lab1.l - lab2.l, lab1.a - lab2.a, lab1.b - lab2.b);
+    `kl`, `kc` and `kh` are the parametric weighting factors for lightness,
+    chroma and hue, `k1` and `k2` scale the chroma and hue tolerances with
+    chroma of the reference color. See [`CIE94::GRAPHIC_ARTS`] and
+    [`CIE94::TEXTILES`] for the standard sets.
 
-        let C1 = hypotf(*lab1.a, *lab1.b);
-        let C2 = hypotf(*lab2.a, *lab2.b);
+    The formula is asymmetric, the first color is the reference. Symmetric
+    variant uses geometric mean of the chromas instead, see
+    [`CIE94::symmetric`].
+
+    Formula: <http://www.brucelindbloom.com/index.html?Eqn_DeltaE_CIE94.html>
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CIE94 {
+    kl: f32,
+    kc: f32,
+    kh: f32,
+    k1: f32,
+    k2: f32,
+    /// Whether chroma of both colors is used to weight the difference.
+    symmetric: bool,
+}
+
+impl CIE94 {
+    pub const GRAPHIC_ARTS: Self = Self::new(1.0, 1.0, 1.0, 0.045, 0.015);
+    pub const TEXTILES: Self = Self::new(2.0, 1.0, 1.0, 0.048, 0.014);
+
+    /**
+        # Panics
+
+        Panics if any of `kl`, `kc` and `kh` is not positive or not finite,
+        or if `k1` or `k2` is negative or not finite.
+    */
+    pub const fn new(kl: f32, kc: f32, kh: f32, k1: f32, k2: f32) -> Self {
+        assert!(
+            is_weight(kl)
+                && is_weight(kc)
+                && is_weight(kh)
+                && is_tolerance_factor(k1)
+                && is_tolerance_factor(k2),
+            "CIE94 weights must be finite, kl, kc and kh positive, k1 and k2 non-negative"
+        );
+        Self {
+            kl,
+            kc,
+            kh,
+            k1,
+            k2,
+            symmetric: false,
+        }
+    }
+
+    /**
+        Same weights, but `dist(c1, c2) == dist(c2, c1)`, e.g. for clustering.
+
+        # Example
+
+        ```
+        use codi_core::color_dist::{CIE94, ColorDistance};
+        use codi_core::color_space::rgb;
+
+        let (c1, c2) = (rgb(200, 30, 30), rgb(120, 90, 90));
+        let alg = CIE94::GRAPHIC_ARTS.symmetric();
+        assert_eq!(alg.dist(c1, c2), alg.dist(c2, c1));
+        assert_ne!(CIE94::GRAPHIC_ARTS.dist(c1, c2), CIE94::GRAPHIC_ARTS.dist(c2, c1));
+        ```
+    */
+    #[must_use]
+    pub const fn symmetric(self) -> Self {
+        Self {
+            symmetric: true,
+            ..self
+        }
+    }
+
+    pub const fn kl(&self) -> f32 {
+        self.kl
+    }
+
+    pub const fn kc(&self) -> f32 {
+        self.kc
+    }
+
+    pub const fn kh(&self) -> f32 {
+        self.kh
+    }
+
+    pub const fn k1(&self) -> f32 {
+        self.k1
+    }
+
+    pub const fn k2(&self) -> f32 {
+        self.k2
+    }
+
+    pub const fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /**
+        Find ΔE94 between the reference and sample colors in CIELAB space.
+    */
+    #[allow(clippy::missing_panics_doc)]
+    pub fn dist_lab(&self, reference: Cielab, sample: Cielab) -> NotNan<f32> {
+        NotNan::new(sqrtf(*self.dist_lab_squared(reference, sample))).unwrap()
+    }
+
+    #[allow(non_snake_case)]
+    fn dist_lab_squared(&self, reference: Cielab, sample: Cielab) -> NotNan<f32> {
+        let (delta_l, delta_a, delta_b) = (
+            reference.l - sample.l,
+            reference.a - sample.a,
+            reference.b - sample.b,
+        );
+
+        let C1 = hypotf(*reference.a, *reference.b);
+        let C2 = hypotf(*sample.a, *sample.b);
+        let C = if self.symmetric { sqrtf(C1 * C2) } else { C1 };
 
         let Cab = C1 - C2;
         let Sl = 1.0;
-        let Sc = 1.0 + K1 * C1;
-        let Sh = 1.0 + K2 * C1;
+        let Sc = 1.0 + self.k1 * C;
+        let Sh = 1.0 + self.k2 * C;
         // https://github.com/zschuessler/DeltaE/issues/9
         let Hab = sqrtf((powf(*delta_a, 2.0) + powf(*delta_b, 2.0) - powf(Cab, 2.0)).max(0.0));
 
-        let dist = powf(*delta_l / (kL * Sl), 2.0)
-            + powf(Cab / (kC * Sc), 2.0)
-            + powf(Hab / (kH * Sh), 2.0);
+        let dist = powf(*delta_l / (self.kl * Sl), 2.0)
+            + powf(Cab / (self.kc * Sc), 2.0)
+            + powf(Hab / (self.kh * Sh), 2.0);
         NotNan::new(dist).unwrap()
     }
 }
 
+impl Default for CIE94 {
+    fn default() -> Self {
+        Self::GRAPHIC_ARTS
+    }
+}
+
+impl ColorDistance for CIE94 {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        self.dist_lab_squared(Cielab::from(c1), Cielab::from(c2))
+    }
+}
+
 impl core::fmt::Display for CIE94 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "CIE94")
+        let weights = Self {
+            symmetric: false,
+            ..*self
+        };
+        if weights == Self::GRAPHIC_ARTS {
+            write!(f, "CIE94")?;
+        } else if weights == Self::TEXTILES {
+            write!(f, "CIE94 Textiles")?;
+        } else {
+            write!(
+                f,
+                "CIE94({}, {}, {}, {}, {})",
+                self.kl, self.kc, self.kh, self.k1, self.k2
+            )?;
+        }
+        if self.symmetric {
+            write!(f, " Symmetric")?;
+        }
+        Ok(())
+    }
+}
+
+/**
+    CMC l:c color difference. It is asymmetric: the first color is the
+    reference (standard) and the tolerance ellipsoid is built around it.
+
+    `l` and `c` are the weights of lightness and chroma. The 2:1 ratio is
+    used for acceptability, e.g. in textile industry, and the 1:1 ratio for
+    perceptibility.
+
+    Formula: <http://www.brucelindbloom.com/index.html?Eqn_DeltaE_CMC.html>
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cmc {
+    l: f32,
+    c: f32,
+}
+
+impl Cmc {
+    /// CMC(2:1), acceptability of the difference.
+    pub const ACCEPTABILITY: Self = Self::new(2.0, 1.0);
+    /// CMC(1:1), perceptibility of the difference.
+    pub const PERCEPTIBILITY: Self = Self::new(1.0, 1.0);
+
+    /**
+        # Panics
+
+        Panics if any of the weights is not positive or not finite.
+    */
+    pub const fn new(l: f32, c: f32) -> Self {
+        assert!(
+            is_weight(l) && is_weight(c),
+            "CMC weights must be positive and finite"
+        );
+        Self { l, c }
+    }
+
+    pub const fn l(&self) -> f32 {
+        self.l
+    }
+
+    pub const fn c(&self) -> f32 {
+        self.c
+    }
+
+    /**
+        Find ΔE CMC between the reference and sample colors in CIELAB space.
+    */
+    #[allow(clippy::missing_panics_doc)]
+    pub fn dist_lab(self, reference: Cielab, sample: Cielab) -> NotNan<f32> {
+        NotNan::new(sqrtf(*self.dist_lab_squared(reference, sample))).unwrap()
+    }
+
+    #[allow(non_snake_case)]
+    fn dist_lab_squared(self, reference: Cielab, sample: Cielab) -> NotNan<f32> {
+        let (L1, a1, b1) = (*reference.l, *reference.a, *reference.b);
+        let (L2, a2, b2) = (*sample.l, *sample.a, *sample.b);
+
+        let (C1, C2) = (hypotf(a1, b1), hypotf(a2, b2));
+        let delta_L = L1 - L2;
+        let delta_C = C1 - C2;
+        let delta_H_pow2 = (powf(a1 - a2, 2.0) + powf(b1 - b2, 2.0) - powf(delta_C, 2.0)).max(0.0);
+
+        let H1 = hue_angle(a1, b1);
+        let T = if (164.0..=345.0).contains(&H1) {
+            0.56 + (0.2 * cosf((H1 + 168.0).to_radians())).abs()
+        } else {
+            0.36 + (0.4 * cosf((H1 + 35.0).to_radians())).abs()
+        };
+        let C1_pow4 = powf(C1, 4.0);
+        let F = sqrtf(C1_pow4 / (C1_pow4 + 1900.0));
+
+        let Sl = if L1 < 16.0 {
+            0.511
+        } else {
+            0.040_975 * L1 / (1.0 + 0.017_65 * L1)
+        };
+        let Sc = 0.0638 * C1 / (1.0 + 0.0131 * C1) + 0.638;
+        let Sh = Sc * (F * T + 1.0 - F);
+
+        let dist = powf(delta_L / (self.l * Sl), 2.0)
+            + powf(delta_C / (self.c * Sc), 2.0)
+            + delta_H_pow2 / powf(Sh, 2.0);
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl ColorDistance for Cmc {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        self.dist_lab_squared(Cielab::from(c1), Cielab::from(c2))
+    }
+}
+
+impl core::fmt::Display for Cmc {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CMC({}:{})", self.l, self.c)
+    }
+}
+
+/**
+    Euclidean distance in the [`Din99`] color space.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Din99Distance;
+
+impl ColorDistance for Din99Distance {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        let (din1, din2) = (Din99::from(c1), Din99::from(c2));
+        let dist = powf(*din1.l - *din2.l, 2.0)
+            + powf(*din1.a - *din2.a, 2.0)
+            + powf(*din1.b - *din2.b, 2.0);
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for Din99Distance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "DIN99")
+    }
+}
+
+/**
+    Euclidean distance in the [`Din99o`] color space.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Din99oDistance;
+
+impl ColorDistance for Din99oDistance {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        let (din1, din2) = (Din99o::from(c1), Din99o::from(c2));
+        let dist = powf(*din1.l - *din2.l, 2.0)
+            + powf(*din1.a - *din2.a, 2.0)
+            + powf(*din1.b - *din2.b, 2.0);
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for Din99oDistance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "DIN99o")
+    }
+}
+
+/**
+    CIEDE2000 color difference.
+
+    `kl`, `kc` and `kh` are the parametric weighting factors for lightness,
+    chroma and hue respectively. They are all equal to `1.0` under reference
+    conditions.
+
+    Formula: <https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/ciede2000noteCRNA.pdf>
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CIEDE2000 {
+    kl: f32,
+    kc: f32,
+    kh: f32,
+}
+
+impl CIEDE2000 {
+    /**
+        # Panics
+
+        Panics if any of the weights is not positive or not finite.
+    */
+    pub const fn new(kl: f32, kc: f32, kh: f32) -> Self {
+        assert!(
+            is_weight(kl) && is_weight(kc) && is_weight(kh),
+            "CIEDE2000 weights must be positive and finite"
+        );
+        Self { kl, kc, kh }
+    }
+
+    pub const fn kl(&self) -> f32 {
+        self.kl
+    }
+
+    pub const fn kc(&self) -> f32 {
+        self.kc
+    }
+
+    pub const fn kh(&self) -> f32 {
+        self.kh
+    }
+
+    /**
+        Find ΔE00 between two colors in CIELAB space.
+    */
+    #[allow(clippy::missing_panics_doc)]
+    pub fn dist_lab(&self, lab1: Cielab, lab2: Cielab) -> NotNan<f32> {
+        NotNan::new(sqrtf(*self.dist_lab_squared(lab1, lab2))).unwrap()
+    }
+
+    #[allow(non_snake_case)]
+    fn dist_lab_squared(&self, lab1: Cielab, lab2: Cielab) -> NotNan<f32> {
+        // 25^7
+        const POW25_7: f32 = 6_103_515_625.0;
+
+        let (L1, a1, b1) = (*lab1.l, *lab1.a, *lab1.b);
+        let (L2, a2, b2) = (*lab2.l, *lab2.a, *lab2.b);
+
+        let C_mean = f32::midpoint(hypotf(a1, b1), hypotf(a2, b2));
+        let C_mean_pow7 = powf(C_mean, 7.0);
+        let G = 0.5 * (1.0 - sqrtf(C_mean_pow7 / (C_mean_pow7 + POW25_7)));
+
+        let (a1, a2) = ((1.0 + G) * a1, (1.0 + G) * a2);
+        let (C1, C2) = (hypotf(a1, b1), hypotf(a2, b2));
+        let (h1, h2) = (hue_angle(a1, b1), hue_angle(a2, b2));
+        let chroma_product = C1 * C2;
+
+        let delta_L = L2 - L1;
+        let delta_C = C2 - C1;
+        let delta_h = if chroma_product == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 - h1 > 180.0 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let delta_H = 2.0 * sqrtf(chroma_product) * sinf((delta_h / 2.0).to_radians());
+
+        let L_mean = f32::midpoint(L1, L2);
+        let C_mean = f32::midpoint(C1, C2);
+        let h_mean = if chroma_product == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            f32::midpoint(h1, h2)
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let T = 1.0 - 0.17 * cosf((h_mean - 30.0).to_radians())
+            + 0.24 * cosf((2.0 * h_mean).to_radians())
+            + 0.32 * cosf((3.0 * h_mean + 6.0).to_radians())
+            - 0.20 * cosf((4.0 * h_mean - 63.0).to_radians());
+        let delta_theta = 30.0 * expf(-powf((h_mean - 275.0) / 25.0, 2.0));
+        let C_mean_pow7 = powf(C_mean, 7.0);
+        let Rc = 2.0 * sqrtf(C_mean_pow7 / (C_mean_pow7 + POW25_7));
+        let L_mean_shift_pow2 = powf(L_mean - 50.0, 2.0);
+        let Sl = 1.0 + 0.015 * L_mean_shift_pow2 / sqrtf(20.0 + L_mean_shift_pow2);
+        let Sc = 1.0 + 0.045 * C_mean;
+        let Sh = 1.0 + 0.015 * C_mean * T;
+        let Rt = -sinf((2.0 * delta_theta).to_radians()) * Rc;
+
+        let (l_term, c_term, h_term) = (
+            delta_L / (self.kl * Sl),
+            delta_C / (self.kc * Sc),
+            delta_H / (self.kh * Sh),
+        );
+        let dist = powf(l_term, 2.0) + powf(c_term, 2.0) + powf(h_term, 2.0) + Rt * c_term * h_term;
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl Default for CIEDE2000 {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+}
+
+impl ColorDistance for CIEDE2000 {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        self.dist_lab_squared(Cielab::from(c1), Cielab::from(c2))
+    }
+}
+
+impl core::fmt::Display for CIEDE2000 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CIEDE2000")
+    }
+}
+
+/**
+    ΔE' color difference, Euclidean distance in the [`Cam16Ucs`] color space
+    under the given viewing conditions.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Cam16UcsDistance {
+    conditions: ViewingConditions,
+    env: Environment,
+}
+
+impl Cam16UcsDistance {
+    pub const SRGB: Self = Self {
+        conditions: ViewingConditions::SRGB,
+        env: Environment::SRGB,
+    };
+
+    pub fn new(conditions: ViewingConditions) -> Self {
+        Self {
+            conditions,
+            env: conditions.environment(),
+        }
+    }
+
+    pub const fn conditions(&self) -> ViewingConditions {
+        self.conditions
+    }
+
+    fn ucs(&self, color: Rgb) -> Cam16Ucs {
+        Cam16::from_xyz_in(color.into(), &self.env).into()
+    }
+}
+
+impl Default for Cam16UcsDistance {
+    fn default() -> Self {
+        Self::SRGB
+    }
+}
+
+impl ColorDistance for Cam16UcsDistance {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        let (ucs1, ucs2) = (self.ucs(c1), self.ucs(c2));
+        let dist = powf(*ucs1.j - *ucs2.j, 2.0)
+            + powf(*ucs1.a - *ucs2.a, 2.0)
+            + powf(*ucs1.b - *ucs2.b, 2.0);
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for Cam16UcsDistance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CAM16-UCS")
     }
 }
 
+/**
+    Euclidean distance in the [`Oklab`] color space multiplied by `100` to
+    have about the same scale as ΔE of the CIE formulas.
+
+    Info: <https://bottosson.github.io/posts/oklab/>
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct OklabDistance;
+
+impl ColorDistance for OklabDistance {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        // 100^2
+        const SCALE: f32 = 10_000.0;
+
+        let (lab1, lab2) = (Oklab::from(c1), Oklab::from(c2));
+        let dist = SCALE
+            * (powf(*lab1.l - *lab2.l, 2.0)
+                + powf(*lab1.a - *lab2.a, 2.0)
+                + powf(*lab1.b - *lab2.b, 2.0));
+        NotNan::new(dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for OklabDistance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Oklab")
+    }
+}
+
+/**
+    ΔE ITP color difference in the [`Ictcp`] color space, where `1.0` is a
+    just noticeable difference. SDR colors are compared at the luminance of
+    [`SDR_WHITE_LUMINANCE`](crate::color_space::SDR_WHITE_LUMINANCE).
+
+    Formula: ITU-R BT.2124
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct ItpDistance;
+
+impl ItpDistance {
+    /**
+        Distance between two colors in the [`Ictcp`] color space, e.g. of
+        the HDR content created with [`Ictcp::from_absolute`].
+    */
+    #[allow(clippy::missing_panics_doc)]
+    pub fn dist_ictcp(self, c1: Ictcp, c2: Ictcp) -> NotNan<f32> {
+        const SCALE: f32 = 720.0;
+
+        let dist = powf(*c1.i - *c2.i, 2.0)
+            + powf(0.5 * (*c1.ct - *c2.ct), 2.0)
+            + powf(*c1.cp - *c2.cp, 2.0);
+        NotNan::new(SCALE * sqrtf(dist)).unwrap()
+    }
+}
+
+impl ColorDistance for ItpDistance {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        let dist = self.dist_ictcp(Ictcp::from(c1), Ictcp::from(c2));
+        NotNan::new(*dist * *dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for ItpDistance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ICtCp")
+    }
+}
+
+/**
+    Color difference `ΔEz` in the [`Jzazbz`] color space multiplied by
+    [`JzazbzDistance::SCALE`] to have about the same scale as ΔE of the CIE
+    formulas, the distance between black and white is `100`. Unscaled `ΔEz`
+    is returned by [`JzazbzDistance::dist_jzazbz`]. SDR colors are compared
+    at the luminance of
+    [`SDR_WHITE_LUMINANCE`](crate::color_space::SDR_WHITE_LUMINANCE).
+
+    The formula uses differences of the chroma and hue, it is the same as
+    Euclidean distance.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct JzazbzDistance;
+
+impl JzazbzDistance {
+    /**
+        Factor of `ΔEz` in [`ColorDistance::dist`], `Jz` of the SDR white is
+        about `0.222`.
+    */
+    pub const SCALE: f32 = 450.0;
+
+    /**
+        Distance between two colors in the [`Jzazbz`] color space without
+        scaling, e.g. of the HDR content created with
+        [`Jzazbz::from_absolute`].
+    */
+    #[allow(clippy::missing_panics_doc)]
+    pub fn dist_jzazbz(self, c1: Jzazbz, c2: Jzazbz) -> NotNan<f32> {
+        let dist =
+            powf(*c1.jz - *c2.jz, 2.0) + powf(*c1.az - *c2.az, 2.0) + powf(*c1.bz - *c2.bz, 2.0);
+        NotNan::new(sqrtf(dist)).unwrap()
+    }
+}
+
+impl ColorDistance for JzazbzDistance {
+    fn dist_squared(&self, c1: Rgb, c2: Rgb) -> NotNan<f32> {
+        let dist = Self::SCALE * *self.dist_jzazbz(Jzazbz::from(c1), Jzazbz::from(c2));
+        NotNan::new(dist * dist).unwrap()
+    }
+}
+
+impl core::fmt::Display for JzazbzDistance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Jzazbz")
+    }
+}
+
+/**
+    Whether `value` can be used as a weighting factor in the denominator.
+*/
+const fn is_weight(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+/**
+    Whether `value` can scale a tolerance with chroma without making it
+    smaller than 1.
+*/
+const fn is_tolerance_factor(value: f32) -> bool {
+    value.is_finite() && value >= 0.0
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::color_space::Rgb;
+    use crate::cam16::Surround;
+    use crate::color_space::{Rgb, Xyz, rgb};
     use crate::html_color::find_closest;
 
     #[test]
-    fn all_algs_same_result() -> Result<(), Box<dyn std::error::Error>> {
-        let tests = [(Rgb::try_from("#81818d")?, Rgb::try_from("#808080")?)];
+    fn closest_color_per_algorithm() -> Result<(), Box<dyn std::error::Error>> {
+        let input = Rgb::try_from("#81818d")?;
+        let (gray, slategray) = (Rgb::try_from("#808080")?, Rgb::try_from("#708090")?);
+
+        for (name, alg) in REGISTRY {
+            // CIE76 and DIN99o weight the small chroma difference higher
+            // than lightness and prefer slategray.
+            let expected = match name {
+                "cie76" | "din99o" => slategray,
+                _ => gray,
+            };
+            assert_eq!(
+                find_closest(alg, input).color.color,
+                expected,
+                "closest html color for {input} with {alg} shoud be {expected}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn find_k_closest_sorted() {
+        let candidates = [
+            Rgb::new(40, 40, 40),
+            Rgb::new(250, 250, 250),
+            Rgb::new(100, 100, 100),
+            Rgb::new(255, 255, 255),
+            Rgb::new(100, 100, 100),
+        ];
+        let target = Rgb::new(80, 80, 80);
+
+        for alg in ALGORITHMS {
+            let closest = alg.find_k_closest(target, &candidates, 3);
+            let indices: Vec<usize> = closest.iter().map(|(idx, _)| *idx).collect();
+            assert_eq!(indices, [2, 4, 0], "algorithm: {alg}");
+            assert!(closest.is_sorted_by_key(|(_, dist)| *dist));
+            assert_eq!(closest[0].1, alg.dist(target, candidates[2]));
+
+            assert_eq!(
+                alg.find_k_closest(target, &candidates, 1)[0].0,
+                alg.find_closest(target, &candidates).unwrap()
+            );
+            assert_eq!(alg.find_k_closest(target, &candidates, 10).len(), 5);
+            assert!(alg.find_k_closest(target, &candidates, 0).is_empty());
+            assert!(alg.find_k_closest(target, &[], 3).is_empty());
+        }
+    }
+
+    #[test]
+    fn find_within_sorted() {
+        let candidates = [
+            Rgb::new(40, 40, 40),
+            Rgb::new(250, 250, 250),
+            Rgb::new(100, 100, 100),
+            Rgb::new(255, 255, 255),
+            Rgb::new(100, 100, 100),
+        ];
+        let target = Rgb::new(252, 252, 252);
+
+        for alg in ALGORITHMS {
+            let max_distance = *alg.dist(target, candidates[3]);
+            let found = alg.find_within(target, &candidates, max_distance);
+            let indices: Vec<usize> = found.iter().map(|(idx, _)| *idx).collect();
+            assert_eq!(indices, [1, 3], "algorithm: {alg}");
+            assert!(found.iter().all(|(_, dist)| **dist <= max_distance));
 
-        for (input, expected) in tests {
-            for alg in ALGORITHMS {
-                assert_eq!(
-                    find_closest(alg, input).color,
-                    expected,
-                    "closest html color for {input} shoud be {expected}"
+            let all = alg.find_within(target, &candidates, f32::INFINITY);
+            assert_eq!(
+                all,
+                alg.find_k_closest(target, &candidates, candidates.len())
+            );
+            assert!(alg.find_within(target, &candidates, 0.0).is_empty());
+            assert!(alg.find_within(target, &[], 100.0).is_empty());
+        }
+    }
+
+    #[test]
+    fn find_algorithm_by_name() {
+        for (idx, (name, alg)) in REGISTRY.iter().enumerate() {
+            assert_eq!(
+                find_algorithm(name).map(ToString::to_string),
+                Some(alg.to_string())
+            );
+            assert_eq!(
+                find_algorithm(&name.to_ascii_uppercase()).map(ToString::to_string),
+                Some(alg.to_string())
+            );
+            assert!(
+                REGISTRY[idx + 1..].iter().all(|(other, _)| other != name),
+                "duplicate name: {name}"
+            );
+        }
+        assert!(find_algorithm("").is_none());
+        assert!(find_algorithm("euclidean improved").is_none());
+    }
+
+    #[test]
+    fn dist_units() {
+        let (black, white) = (Rgb::new(0, 0, 0), Rgb::new(255, 255, 255));
+        let gray = Rgb::new(128, 128, 128);
+
+        assert!((*Euclidean.dist(black, white) - 441.673).abs() < 1e-3);
+        assert!((*OklabDistance.dist(black, white) - 100.0).abs() < 1e-2);
+        assert!((*CIE76.dist(black, white) - 100.0).abs() < 1e-2);
+        assert!((*CIE94::default().dist(black, white) - 100.0).abs() < 1e-2);
+        assert!((*Din99Distance.dist(black, white) - 100.0).abs() < 1e-2);
+        assert!((*Din99oDistance.dist(black, white) - 100.0).abs() < 1e-2);
+        assert!((*CIEDE2000::default().dist(black, white) - 100.0).abs() < 1e-2);
+        assert!((*JzazbzDistance.dist(black, white) - 100.0).abs() < 0.5);
+        // White is not fully adapted and keeps a bit of colorfulness.
+        assert!((*Cam16UcsDistance::default().dist(black, white) - 100.0).abs() < 0.1);
+        for alg in ALGORITHMS {
+            assert!(alg.dist(gray, gray).abs() < 1e-6, "algorithm: {alg}");
+            let (dist, dist_squared) = (alg.dist(black, gray), alg.dist_squared(black, gray));
+            assert!(
+                (*dist * *dist - *dist_squared).abs() <= *dist_squared * 1e-5,
+                "algorithm: {alg}"
+            );
+        }
+    }
+
+    #[test]
+    fn perception_thresholds() {
+        assert_eq!(Perception::from_delta_e(0.0), Perception::Imperceptible);
+        assert_eq!(Perception::from_delta_e(1.0), Perception::BarelyNoticeable);
+        assert_eq!(Perception::from_delta_e(1.9), Perception::BarelyNoticeable);
+        assert_eq!(Perception::from_delta_e(2.0), Perception::Noticeable);
+        assert_eq!(Perception::from_delta_e(10.0), Perception::Different);
+        assert_eq!(
+            Perception::between(Rgb::new(12, 34, 56), Rgb::new(12, 34, 56)),
+            Perception::Imperceptible
+        );
+        assert_eq!(Perception::Noticeable.to_string(), "noticeable");
+    }
+
+    #[test]
+    fn cie94_parameters() {
+        // Test data from python-colormath.
+        let (reference, sample) = (lab(0.9, 16.3, -2.22), lab(0.7, 14.2, -1.8));
+        assert!((*CIE94::GRAPHIC_ARTS.dist_lab(reference, sample) - 1.249_29).abs() < 1e-3);
+
+        // Lightness difference is halved for textiles.
+        let (reference, sample) = (lab(50.0, 0.0, 0.0), lab(60.0, 0.0, 0.0));
+        assert!((*CIE94::GRAPHIC_ARTS.dist_lab(reference, sample) - 10.0).abs() < 1e-4);
+        assert!((*CIE94::TEXTILES.dist_lab(reference, sample) - 5.0).abs() < 1e-4);
+
+        let (c1, c2) = (lab(50.0, 60.0, 10.0), lab(40.0, 20.0, 30.0));
+        let symmetric = CIE94::TEXTILES.symmetric();
+        assert_eq!(symmetric.dist_lab(c1, c2), symmetric.dist_lab(c2, c1));
+        let asymmetric = CIE94::TEXTILES;
+        assert!((*asymmetric.dist_lab(c1, c2) - *asymmetric.dist_lab(c2, c1)).abs() > 1.0);
+
+        assert_eq!(CIE94::default().to_string(), "CIE94");
+        assert_eq!(symmetric.to_string(), "CIE94 Textiles Symmetric");
+        assert_eq!(
+            CIE94::new(1.0, 1.0, 1.0, 0.05, 0.02).to_string(),
+            "CIE94(1, 1, 1, 0.05, 0.02)"
+        );
+    }
+
+    #[test]
+    fn cmc_reference_values() {
+        // Test data from python-colormath.
+        let (reference, sample) = (lab(0.9, 16.3, -2.22), lab(0.7, 14.2, -1.8));
+        assert!((*Cmc::ACCEPTABILITY.dist_lab(reference, sample) - 1.442_605).abs() < 1e-3);
+        assert!((*Cmc::PERCEPTIBILITY.dist_lab(reference, sample) - 1.481_89).abs() < 1e-3);
+
+        // Test data from colour-science, lightness of the reference is above 16.
+        let reference = lab(100.0, 21.572_104, 272.228_2);
+        let tests = [
+            (lab(100.0, 426.679_45, 72.395_91), 172.704_77),
+            (lab(100.0, 74.052_17, 276.453_2), 20.597_327),
+            (lab(100.0, 8.322_82, -73.582_98), 121.718_41),
+        ];
+        for (sample, expected) in tests {
+            let actual = *Cmc::ACCEPTABILITY.dist_lab(reference, sample);
+            assert!((actual / expected - 1.0).abs() < 1e-4, "{sample}: {actual}");
+        }
+
+        // Hue of the reference in range [164, 345] uses the other hue weight,
+        // expected values are from the formula evaluated in double precision,
+        // which reproduces the published values above.
+        let blue = lab(100.0, 8.322_82, -73.582_98);
+        let (teal, sample) = (lab(50.0, -40.0, -20.0), lab(55.0, -35.0, -25.0));
+        let tests = [
+            (blue, reference, Cmc::ACCEPTABILITY, 173.786_79),
+            (teal, sample, Cmc::ACCEPTABILITY, 4.441_114),
+            (teal, sample, Cmc::PERCEPTIBILITY, 5.962_714),
+        ];
+        for (reference, sample, cmc, expected) in tests {
+            let actual = *cmc.dist_lab(reference, sample);
+            assert!(
+                (actual / expected - 1.0).abs() < 1e-4,
+                "{cmc} {reference}: {actual}"
+            );
+        }
+
+        // Lightness difference is halved by the 2:1 weights.
+        let (reference, sample) = (lab(50.0, 20.0, 30.0), lab(55.0, 20.0, 30.0));
+        let acceptability = *Cmc::ACCEPTABILITY.dist_lab(reference, sample);
+        let perceptibility = *Cmc::PERCEPTIBILITY.dist_lab(reference, sample);
+        assert!((perceptibility / acceptability - 2.0).abs() < 1e-4);
+
+        // Tolerance depends on the reference color only.
+        let (reference, sample) = (lab(50.0, 60.0, 10.0), lab(50.0, 20.0, 30.0));
+        assert!(
+            (*Cmc::PERCEPTIBILITY.dist_lab(reference, sample)
+                - *Cmc::PERCEPTIBILITY.dist_lab(sample, reference))
+            .abs()
+                > 1.0
+        );
+        assert_eq!(Cmc::ACCEPTABILITY.to_string(), "CMC(2:1)");
+    }
+
+    #[test]
+    fn cie76_and_din99_values() {
+        let (red, blue) = (rgb(255, 0, 0), rgb(0, 0, 255));
+        assert!((*CIE76.dist(red, blue) - 176.31).abs() < 0.05);
+        assert!((*Din99Distance.dist(red, blue) - 52.689).abs() < 1e-2);
+        assert!((*Din99oDistance.dist(red, blue) - 74.41).abs() < 1e-2);
+
+        // DIN99 compresses large chroma differences, unlike CIE76.
+        let gray = rgb(128, 128, 128);
+        assert!(*Din99Distance.dist(red, gray) < *CIE76.dist(red, gray) / 2.0);
+        assert_eq!(CIE76.to_string(), "CIE76");
+        assert_eq!(Din99oDistance.to_string(), "DIN99o");
+    }
+
+    #[test]
+    fn cam16_ucs_viewing_conditions() {
+        let (coral, tomato) = (rgb(255, 127, 80), rgb(255, 99, 71));
+        let average = Cam16UcsDistance::default();
+        let dark = Cam16UcsDistance::new(ViewingConditions::SRGB.with_surround(Surround::Dark));
+
+        assert!((*average.dist(coral, tomato) - *average.dist(tomato, coral)).abs() < 1e-4);
+        assert!((*average.dist(coral, tomato) - *dark.dist(coral, tomato)).abs() > 0.1);
+        assert_eq!(average.to_string(), "CAM16-UCS");
+    }
+
+    #[test]
+    fn hdr_distances() {
+        // Same color at different luminance levels.
+        let (white, bright) = (
+            Xyz::new(0.95, 1.0, 1.09).unwrap(),
+            Xyz::new(9.5, 10.0, 10.9).unwrap(),
+        );
+        let itp = ItpDistance.dist_ictcp(Ictcp::from(white), Ictcp::from(bright));
+        let jz = JzazbzDistance.dist_jzazbz(Jzazbz::from(white), Jzazbz::from(bright));
+        assert!(*itp > 100.0 && *jz > 0.1, "{itp} {jz}");
+
+        // Small differences are around the JND.
+        let (c1, c2) = (rgb(128, 128, 128), rgb(130, 128, 128));
+        assert!((0.5..3.0).contains(&*ItpDistance.dist(c1, c2)));
+        assert!((0.2..3.0).contains(&*JzazbzDistance.dist(c1, c2)));
+    }
+
+    fn lab(l: f32, a: f32, b: f32) -> Cielab {
+        Cielab::new(l, a, b).unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "CIEDE2000 weights must be positive and finite")]
+    fn ciede2000_zero_weight() {
+        let _ = CIEDE2000::new(0.0, 1.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "CIEDE2000 weights must be positive and finite")]
+    fn ciede2000_infinite_weight() {
+        let _ = CIEDE2000::new(1.0, 1.0, f32::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "CIE94 weights must be finite")]
+    fn cie94_zero_weight() {
+        let _ = CIE94::new(0.0, 1.0, 1.0, 0.045, 0.015);
+    }
+
+    #[test]
+    #[should_panic(expected = "CIE94 weights must be finite")]
+    fn cie94_negative_tolerance_factor() {
+        let _ = CIE94::new(1.0, 1.0, 1.0, -0.045, 0.015);
+    }
+
+    #[test]
+    #[should_panic(expected = "CMC weights must be positive and finite")]
+    fn cmc_zero_weight() {
+        let _ = Cmc::new(0.0, 1.0);
+    }
+
+    // Test data from "The CIEDE2000 Color-Difference Formula: Implementation
+    // Notes, Supplementary Test Data, and Mathematical Observations" by
+    // G. Sharma, W. Wu, E. N. Dalal.
+    #[test]
+    fn ciede2000_sharma_test_data() {
+        #[rustfmt::skip]
+        let tests = [
+            ((50.0000, 2.6772, -79.7751), (50.0000, 0.0000, -82.7485), 2.0425),
+            ((50.0000, 3.1571, -77.2803), (50.0000, 0.0000, -82.7485), 2.8615),
+            ((50.0000, 2.8361, -74.0200), (50.0000, 0.0000, -82.7485), 3.4412),
+            ((50.0000, -1.3802, -84.2814), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, -1.1848, -84.8006), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, -0.9009, -85.5211), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, 0.0000, 0.0000), (50.0000, -1.0000, 2.0000), 2.3669),
+            ((50.0000, -1.0000, 2.0000), (50.0000, 0.0000, 0.0000), 2.3669),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0009), 7.1792),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0010), 7.1792),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0011), 7.2195),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0012), 7.2195),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0009, -2.4900), 4.8045),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0010, -2.4900), 4.8045),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0011, -2.4900), 4.7461),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 0.0000, -2.5000), 4.3065),
+            ((50.0000, 2.5000, 0.0000), (73.0000, 25.0000, -18.0000), 27.1492),
+            ((50.0000, 2.5000, 0.0000), (61.0000, -5.0000, 29.0000), 22.8977),
+            ((50.0000, 2.5000, 0.0000), (56.0000, -27.0000, -3.0000), 31.9030),
+            ((50.0000, 2.5000, 0.0000), (58.0000, 24.0000, 15.0000), 19.4535),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.1736, 0.5854), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.2972, 0.0000), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 1.8634, 0.5757), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.2592, 0.3350), 1.0000),
+            ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+            ((63.0109, -31.0961, -5.8663), (62.8187, -29.7946, -4.0864), 1.2630),
+            ((61.2901, 3.7196, -5.3901), (61.4292, 2.2480, -4.9620), 1.8731),
+            ((35.0831, -44.1164, 3.7933), (35.0232, -40.0716, 1.5901), 1.8645),
+            ((22.7233, 20.0904, -46.6940), (23.0331, 14.9730, -42.5619), 2.0373),
+            ((36.4612, 47.8580, 18.3852), (36.2715, 50.5065, 21.2231), 1.4146),
+            ((90.8027, -2.0831, 1.4410), (91.1528, -1.6435, 0.0447), 1.4441),
+            ((90.9257, -0.5406, -0.9208), (88.6381, -0.8985, -0.7239), 1.5381),
+            ((6.7747, -0.2908, -2.4247), (5.8714, -0.0985, -2.2286), 0.6377),
+            ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+
+        let alg = CIEDE2000::default();
+        for ((l1, a1, b1), (l2, a2, b2), expected) in tests {
+            let (lab1, lab2) = (lab(l1, a1, b1), lab(l2, a2, b2));
+            for (c1, c2) in [(lab1, lab2), (lab2, lab1)] {
+                let actual = *alg.dist_lab(c1, c2);
+                assert!(
+                    (actual - expected).abs() < 1e-4,
+                    "{c1:?} {c2:?}: expected {expected}, got {actual}"
                 );
             }
         }
-        Ok(())
     }
 }
@@ -1,15 +1,12 @@
-use libm::{cbrtf, powf, roundf};
+use libm::{atan2f, cbrtf, cosf, hypotf, log1pf, powf, roundf, sinf};
 use ordered_float::NotNan;
 
+use crate::illuminant::{Adaptation, Illuminant};
+use crate::math_utils::rem_euclid;
+use crate::rgb_space::{PQ_M2, RgbSpace, TransferFunction, pq_encode};
+
 const HEX_COLOR_LEN: usize = 6;
 
-const XYZ_ILLUMINANT_D65: Xyz = unsafe {
-    Xyz {
-        x: NotNan::new_unchecked(0.950_489),
-        y: NotNan::new_unchecked(1.0),
-        z: NotNan::new_unchecked(1.08884),
-    }
-};
 const LAB_XYZ_DELTA: f32 = 6.0 / 29.0;
 const LAB_XYZ_DELTA_POW2: f32 = LAB_XYZ_DELTA * LAB_XYZ_DELTA;
 const LAB_XYZ_DELTA_POW3: f32 = LAB_XYZ_DELTA_POW2 * LAB_XYZ_DELTA;
@@ -18,6 +15,9 @@ const LAB_XYZ_DELTA_POW3: f32 = LAB_XYZ_DELTA_POW2 * LAB_XYZ_DELTA;
 pub enum Error {
     HexColorWrongLen(usize),
     NotAsciiHexDigit(u8),
+    NotANumber,
+    /// Color component is infinite.
+    NotFinite,
 }
 
 impl core::fmt::Display for Error {
@@ -30,6 +30,8 @@ impl core::fmt::Display for Error {
                 HEX_COLOR_LEN + 1
             ),
             Self::NotAsciiHexDigit(dig) => write!(f, "{dig} is not ascii hexadecimal digit"),
+            Self::NotANumber => write!(f, "Color component is NaN"),
+            Self::NotFinite => write!(f, "Color component is infinite"),
         }
     }
 }
@@ -55,6 +57,61 @@ impl Rgb {
         }
     }
 
+    /**
+        Converts gamma-corrected 8-bit channels into linear sRGB in range [0, 1].
+    */
+    fn to_linear(self) -> [f32; 3] {
+        self.to_unit().map(|col| {
+            if col <= 0.04045 {
+                col / 12.92
+            } else {
+                powf((col + 0.055) / 1.055, 2.4)
+            }
+        })
+    }
+
+    /**
+        Converts 8-bit channels into gamma-corrected channels in range [0, 1].
+    */
+    pub(crate) fn to_unit(self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|col| f32::from(col) / f32::from(u8::MAX))
+    }
+
+    /**
+        Converts linear sRGB in range [0, 1] into gamma-corrected 8-bit channels,
+        out of range values are clamped.
+    */
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_linear([r, g, b]: [f32; 3]) -> Self {
+        let gamma_cor = |col: f32| {
+            let col = col.clamp(0.0, 1.0);
+            let col = if col <= 0.003_130_8 {
+                col * 12.92
+            } else {
+                powf(col, 1.0 / 2.4) * 1.055 - 0.055
+            };
+            roundf(col * f32::from(u8::MAX)) as u8
+        };
+
+        Self {
+            r: gamma_cor(r),
+            g: gamma_cor(g),
+            b: gamma_cor(b),
+        }
+    }
+
+    /**
+        Converts gamma-corrected channels in range [0, 1] to 8-bit channels,
+        out of range values are clamped.
+    */
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_unit([r, g, b]: [f32; 3]) -> Self {
+        let to_u8 = |col: f32| roundf(col.clamp(0.0, 1.0) * f32::from(u8::MAX)) as u8;
+        Self::new(to_u8(r), to_u8(g), to_u8(b))
+    }
+
     pub fn for_each<F: Fn(Self)>(func: F) {
         for r in 0..=u8::MAX {
             for g in 0..=u8::MAX {
@@ -67,18 +124,18 @@ impl Rgb {
 }
 
 impl core::str::FromStr for Rgb {
-    type Err = Error;
+    type Err = crate::css::Error;
 
     fn from_str(value: &str) -> core::result::Result<Self, Self::Err> {
-        value.as_bytes().try_into()
+        value.try_into()
     }
 }
 
 impl TryFrom<&str> for Rgb {
-    type Error = Error;
+    type Error = crate::css::Error;
 
     /**
-        Convert hex colors into Rgb.
+        Convert CSS colors into Rgb, see [`css::parse`](crate::css::parse).
 
         Hex strings format can be either with leading '#' symbol or not.
 
@@ -88,10 +145,15 @@ impl TryFrom<&str> for Rgb {
         use codi_core::color_space::Rgb;
         assert_eq!("#00a0f0".try_into(), Ok(Rgb::new(0, 160, 240)));
         assert_eq!("fFfFfF".try_into(), Ok(Rgb::new(255, 255, 255)));
+        assert_eq!("#0af".try_into(), Ok(Rgb::new(0, 170, 255)));
+        assert_eq!("rgb(0 160 240)".parse(), Ok(Rgb::new(0, 160, 240)));
+        assert_eq!("coral".parse(), Ok(Rgb::new(255, 127, 80)));
+        assert_eq!("rgb(0 0)".parse::<Rgb>().unwrap_err().offset, 7);
         ```
     */
     fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
-        value.as_bytes().try_into()
+        // Most of the colors are 6-digit hex, they don't need the parser.
+        Self::try_from(value.as_bytes()).or_else(|_| crate::css::parse(value))
     }
 }
 
@@ -122,37 +184,10 @@ impl TryFrom<[u8; 6]> for Rgb {
 }
 
 impl From<Xyz> for Rgb {
-    #[allow(clippy::cast_sign_loss)]
-    #[allow(clippy::cast_possible_truncation)]
-    /// <https://en.wikipedia.org/wiki/SRGB#From_CIE_XYZ_to_sRGB>
+    /// Colors outside of the sRGB gamut are clipped, see
+    /// [`GamutMapping`](crate::gamut::GamutMapping) for better mapping.
     fn from(value: Xyz) -> Self {
-        const COEF: [[f32; 3]; 3] = [
-            [3.2406, -1.5372, -0.4986],
-            [-0.9689, 1.8758, 0.0415],
-            [0.0557, -0.2040, 1.0570],
-        ];
-
-        let gamma_cor = |col: f32| {
-            if col <= 0.003_130_8 {
-                col * 12.92
-            } else {
-                powf(col, 1.0 / 2.4) * 1.055 - 0.055
-            }
-        };
-
-        let scaled_col: [[f32; 1]; 3] = [[*value.x], [*value.y], [*value.z]];
-        let res = crate::math_utils::matrix_mul(&COEF, &scaled_col);
-        let corrected = (
-            gamma_cor(res[0][0]) * f32::from(u8::MAX),
-            gamma_cor(res[1][0]) * f32::from(u8::MAX),
-            gamma_cor(res[2][0]) * f32::from(u8::MAX),
-        );
-
-        Self {
-            r: roundf(corrected.0) as u8,
-            g: roundf(corrected.1) as u8,
-            b: roundf(corrected.2) as u8,
-        }
+        Self::from_linear(value.to_linear_srgb())
     }
 }
 
@@ -162,6 +197,38 @@ impl From<Cielab> for Rgb {
     }
 }
 
+impl From<Oklab> for Rgb {
+    /// Colors outside of the sRGB gamut are clipped, see
+    /// [`GamutMapping`](crate::gamut::GamutMapping) for better mapping.
+    fn from(value: Oklab) -> Self {
+        Self::from_linear(value.to_linear_srgb())
+    }
+}
+
+impl From<Oklch> for Rgb {
+    fn from(value: Oklch) -> Self {
+        Self::from(Oklab::from(value))
+    }
+}
+
+impl From<Hsl> for Rgb {
+    fn from(value: Hsl) -> Self {
+        Self::from_unit(value.to_srgb())
+    }
+}
+
+impl From<Hsv> for Rgb {
+    fn from(value: Hsv) -> Self {
+        Self::from_unit(value.to_srgb())
+    }
+}
+
+impl From<Hwb> for Rgb {
+    fn from(value: Hwb) -> Self {
+        Self::from_unit(value.to_srgb())
+    }
+}
+
 impl core::fmt::Display for Rgb {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Rgb({}, {}, {})", self.r, self.g, self.b)
@@ -191,32 +258,53 @@ pub struct Xyz {
     pub(crate) z: NotNan<f32>,
 }
 
-impl From<Rgb> for Xyz {
-    /// <https://en.wikipedia.org/wiki/SRgb#From_sRgb_to_CIE_XYZ>
-    fn from(value: Rgb) -> Self {
-        // http://www.brucelindbloom.com/index.html?Eqn_Rgb_XYZ_Matrix.html
-        const COEF: [[f32; 3]; 3] = [
-            [0.4124, 0.3576, 0.1805],
-            [0.2126, 0.7152, 0.0722],
-            [0.0193, 0.1192, 0.9505],
-        ];
+impl Xyz {
+    /**
+        Creates color from tristimulus values relative to D65 white point with
+        `Y` of `1.0`.
 
-        let to_linear = |col: f32| {
-            if col <= 0.04045 {
-                col / 12.92
-            } else {
-                powf((col + 0.055) / 1.055, 2.4)
-            }
-        };
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(x: f32, y: f32, z: f32) -> Result<Self> {
+        Ok(Self {
+            x: finite(x)?,
+            y: finite(y)?,
+            z: finite(z)?,
+        })
+    }
+
+    pub fn x(&self) -> f32 {
+        *self.x
+    }
+
+    pub fn y(&self) -> f32 {
+        *self.y
+    }
+
+    pub fn z(&self) -> f32 {
+        *self.z
+    }
 
-        let (r, g, b) = (
-            f32::from(value.r) / f32::from(u8::MAX),
-            f32::from(value.g) / f32::from(u8::MAX),
-            f32::from(value.b) / f32::from(u8::MAX),
+    /**
+        Converts color into linear sRGB, channels of the colors outside of
+        the sRGB gamut are out of range [0, 1].
+    */
+    pub(crate) fn to_linear_srgb(self) -> [f32; 3] {
+        let res = crate::math_utils::matrix_mul(
+            &RgbSpace::SRGB.xyz_to_linear,
+            &[[*self.x], [*self.y], [*self.z]],
         );
+        [res[0][0], res[1][0], res[2][0]]
+    }
 
-        let lin_col: [[f32; 1]; 3] = [[to_linear(r)], [to_linear(g)], [to_linear(b)]];
-        let res = crate::math_utils::matrix_mul(&COEF, &lin_col);
+    /**
+        Converts finite linear sRGB channels into color.
+    */
+    pub(crate) fn from_linear_srgb([r, g, b]: [f32; 3]) -> Self {
+        let res = crate::math_utils::matrix_mul(&RgbSpace::SRGB.linear_to_xyz, &[[r], [g], [b]]);
 
         unsafe {
             Self {
@@ -226,35 +314,78 @@ impl From<Rgb> for Xyz {
             }
         }
     }
-}
 
-impl From<Cielab> for Xyz {
-    /// <https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIELAB_to_CIEXYZ>
-    fn from(value: Cielab) -> Self {
-        let f_inv = |t: f32| {
-            if t > LAB_XYZ_DELTA {
-                powf(t, 3.0)
-            } else {
-                3.0 * LAB_XYZ_DELTA_POW2 * (t - 4.0 / 29.0)
+    /**
+        Multiplies all tristimulus values by `factor`.
+    */
+    pub(crate) fn scale(self, factor: f32) -> Self {
+        unsafe {
+            Self {
+                x: NotNan::new_unchecked(*self.x * factor),
+                y: NotNan::new_unchecked(*self.y * factor),
+                z: NotNan::new_unchecked(*self.z * factor),
             }
-        };
+        }
+    }
+
+    /**
+        Converts color relative to `src` white into the color that looks the
+        same relative to `dst` white.
+
+        # Example
 
-        let l_common: f32 = (*value.l + 16.0) / 116.0;
+        ```
+        use codi_core::color_space::{Cielab, Rgb, Xyz};
+        use codi_core::illuminant::{Adaptation, Illuminant};
 
-        let x = *XYZ_ILLUMINANT_D65.x * f_inv(l_common + *value.a / 500.0);
-        let y = *XYZ_ILLUMINANT_D65.y * f_inv(l_common);
-        let z = *XYZ_ILLUMINANT_D65.z * f_inv(l_common - *value.b / 200.0);
+        // Lab relative to D50 as reported by the print tools.
+        let xyz = Xyz::from(Rgb::new(255, 0, 0));
+        let xyz_d50 = xyz.adapt(Illuminant::D65, Illuminant::D50, Adaptation::Bradford);
+        let lab = Cielab::from_xyz(xyz_d50, Illuminant::D50);
+        assert_eq!(format!("{lab:.0}"), "Cielab(54, 81, 70)");
+        ```
+    */
+    #[must_use]
+    pub fn adapt(self, src: Illuminant, dst: Illuminant, method: Adaptation) -> Self {
+        let matrix = method.matrix(src, dst);
+        let res = crate::math_utils::matrix_mul(&matrix, &[[*self.x], [*self.y], [*self.z]]);
 
         unsafe {
             Self {
-                x: NotNan::new_unchecked(x),
-                y: NotNan::new_unchecked(y),
-                z: NotNan::new_unchecked(z),
+                x: NotNan::new_unchecked(res[0][0]),
+                y: NotNan::new_unchecked(res[1][0]),
+                z: NotNan::new_unchecked(res[2][0]),
             }
         }
     }
 }
 
+impl From<Rgb> for Xyz {
+    fn from(value: Rgb) -> Self {
+        Self::from_linear_srgb(value.to_linear())
+    }
+}
+
+impl From<Cielab> for Xyz {
+    fn from(value: Cielab) -> Self {
+        value.to_xyz(Illuminant::D65)
+    }
+}
+
+impl TryFrom<[f32; 3]> for Xyz {
+    type Error = Error;
+
+    fn try_from([x, y, z]: [f32; 3]) -> Result<Self> {
+        Self::new(x, y, z)
+    }
+}
+
+impl core::fmt::Display for Xyz {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Xyz", [self.x, self.y, self.z])
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Cielab {
     pub(crate) l: NotNan<f32>,
@@ -262,9 +393,45 @@ pub struct Cielab {
     pub(crate) b: NotNan<f32>,
 }
 
-impl From<Xyz> for Cielab {
+impl Cielab {
+    /**
+        Creates color from lightness in range [0, 100] and `a`, `b` axes
+        relative to D65 white point.
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(l: f32, a: f32, b: f32) -> Result<Self> {
+        Ok(Self {
+            l: finite(l)?,
+            a: finite(a)?,
+            b: finite(b)?,
+        })
+    }
+
+    pub fn l(&self) -> f32 {
+        *self.l
+    }
+
+    pub fn a(&self) -> f32 {
+        *self.a
+    }
+
+    pub fn b(&self) -> f32 {
+        *self.b
+    }
+
+    /**
+        Converts color relative to the `white` point, it is
+        [`Illuminant::D65`] for the [`From`] conversion. To get Lab for
+        another white, [`Xyz::adapt`] color to it first.
+
+        <https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIEXYZ_to_CIELAB>
+    */
     #[allow(clippy::many_single_char_names)]
-    fn from(value: Xyz) -> Self {
+    pub fn from_xyz(value: Xyz, white: Illuminant) -> Self {
         let f = |t: f32| {
             if t > LAB_XYZ_DELTA_POW3 {
                 cbrtf(t)
@@ -273,12 +440,13 @@ impl From<Xyz> for Cielab {
             }
         };
 
+        let white = white.white_point();
         let (x, y, z) = (*value.x, *value.y, *value.z);
 
         let (l, a, b) = (
-            116.0 * f(y / *XYZ_ILLUMINANT_D65.y) - 16.0,
-            500.0 * (f(x / *XYZ_ILLUMINANT_D65.x) - f(y / *XYZ_ILLUMINANT_D65.y)),
-            200.0 * (f(y / *XYZ_ILLUMINANT_D65.y) - f(z / *XYZ_ILLUMINANT_D65.z)),
+            116.0 * f(y / *white.y) - 16.0,
+            500.0 * (f(x / *white.x) - f(y / *white.y)),
+            200.0 * (f(y / *white.y) - f(z / *white.z)),
         );
 
         unsafe {
@@ -289,6 +457,43 @@ impl From<Xyz> for Cielab {
             }
         }
     }
+
+    /**
+        Converts color to XYZ relative to the `white` point, see
+        [`Cielab::from_xyz`].
+
+        <https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIELAB_to_CIEXYZ>
+    */
+    pub fn to_xyz(self, white: Illuminant) -> Xyz {
+        let f_inv = |t: f32| {
+            if t > LAB_XYZ_DELTA {
+                powf(t, 3.0)
+            } else {
+                3.0 * LAB_XYZ_DELTA_POW2 * (t - 4.0 / 29.0)
+            }
+        };
+
+        let white = white.white_point();
+        let l_common: f32 = (*self.l + 16.0) / 116.0;
+
+        let x = *white.x * f_inv(l_common + *self.a / 500.0);
+        let y = *white.y * f_inv(l_common);
+        let z = *white.z * f_inv(l_common - *self.b / 200.0);
+
+        unsafe {
+            Xyz {
+                x: NotNan::new_unchecked(x),
+                y: NotNan::new_unchecked(y),
+                z: NotNan::new_unchecked(z),
+            }
+        }
+    }
+}
+
+impl From<Xyz> for Cielab {
+    fn from(value: Xyz) -> Self {
+        Self::from_xyz(value, Illuminant::D65)
+    }
 }
 
 impl From<Rgb> for Cielab {
@@ -297,71 +502,1014 @@ impl From<Rgb> for Cielab {
     }
 }
 
-const fn u8_from_two_hex(hex1: u8, hex2: u8) -> Result<u8> {
-    match (hex_byte_to_dec(hex1), hex_byte_to_dec(hex2)) {
-        (Ok(v1), Ok(v2)) => Ok(v1 * 16 + v2),
-        (Err(err), _) | (_, Err(err)) => Err(err),
+impl TryFrom<[f32; 3]> for Cielab {
+    type Error = Error;
+
+    fn try_from([l, a, b]: [f32; 3]) -> Result<Self> {
+        Self::new(l, a, b)
+    }
+}
+
+impl core::fmt::Display for Cielab {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Cielab", [self.l, self.a, self.b])
     }
 }
 
 /**
-    Converts ascii hex digit to the decimal number.
+    DIN99 color space, a logarithmic transformation of [`Cielab`], where
+    Euclidean distance is perceptually uniform.
+
+    Formula: DIN 6176, <https://de.wikipedia.org/wiki/DIN99-Farbraum>
 */
-const fn hex_byte_to_dec(hex: u8) -> Result<u8> {
-    match hex {
-        b'0'..=b'9' => Ok(hex - b'0'),
-        b'a'..=b'f' => Ok(hex - b'a' + 10),
-        b'A'..=b'F' => Ok(hex - b'A' + 10),
-        _ => Err(Error::NotAsciiHexDigit(hex)),
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Din99 {
+    pub(crate) l: NotNan<f32>,
+    pub(crate) a: NotNan<f32>,
+    pub(crate) b: NotNan<f32>,
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+impl Din99 {
+    pub fn l(&self) -> f32 {
+        *self.l
+    }
 
-    // Check with and without "#" prefix, lowercase and uppercase. The behavior
-    // should be the same.
-    #[test]
-    fn rgb_from_hex_some() {
-        let tests = [
-            ("#000000", rgb(0, 0, 0)),
-            ("#FFfFff", rgb(255, 255, 255)),
-            ("#010100", rgb(1, 1, 0)),
-            ("#010a00", rgb(1, 10, 0)),
-            ("#00fF00", rgb(0, 255, 0)),
-            ("#abcdef", rgb(171, 205, 239)),
-        ];
-        for (case, expected) in tests {
-            from_hex_assert_ok(case, expected);
-            from_hex_assert_ok(&case.to_ascii_lowercase(), expected);
-            from_hex_assert_ok(&case.to_ascii_uppercase(), expected);
-            from_hex_assert_ok(&case[1..].to_ascii_lowercase(), expected);
-            from_hex_assert_ok(&case[1..].to_ascii_uppercase(), expected);
-        }
+    pub fn a(&self) -> f32 {
+        *self.a
     }
 
-    fn from_hex_assert_ok(input: &str, expected: Rgb) {
-        assert_eq!(
-            Rgb::try_from(input.as_bytes()),
-            Ok(expected),
-            "input: {input}"
-        );
+    pub fn b(&self) -> f32 {
+        *self.b
     }
+}
 
-    #[test]
-    fn rgb_from_hex_error() {
-        let tests = [
-            "0000000", "#00000", "#00000g", "-000000", "#123 45", "123456 ",
-        ];
-        for test in tests {
-            assert!(Rgb::try_from(test.as_bytes()).is_err(), "input: {test}");
+impl From<Cielab> for Din99 {
+    fn from(value: Cielab) -> Self {
+        let [l, a, b] = din99_from_lab(
+            value,
+            &Din99Params {
+                l_scale: 105.509,
+                l_log: 0.0158,
+                rotation: 16.0,
+                f_scale: 0.7,
+                c_log: 0.045,
+                c_scale: 0.045,
+                hue_rotation: 0.0,
+            },
+        );
+        unsafe {
+            Self {
+                l: NotNan::new_unchecked(l),
+                a: NotNan::new_unchecked(a),
+                b: NotNan::new_unchecked(b),
+            }
         }
     }
+}
 
-    #[test]
-    fn hex_byte_to_dec_ok() {
-        assert_eq!(hex_byte_to_dec(b'0'), Ok(0));
+impl From<Rgb> for Din99 {
+    fn from(value: Rgb) -> Self {
+        Self::from(Cielab::from(value))
+    }
+}
+
+impl core::fmt::Display for Din99 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Din99", [self.l, self.a, self.b])
+    }
+}
+
+/**
+    Revision of the [`Din99`] color space with better uniformity of the
+    blue and saturated colors.
+
+    Formula: DIN 6176:2001, <https://de.wikipedia.org/wiki/DIN99-Farbraum>
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Din99o {
+    pub(crate) l: NotNan<f32>,
+    pub(crate) a: NotNan<f32>,
+    pub(crate) b: NotNan<f32>,
+}
+
+impl Din99o {
+    pub fn l(&self) -> f32 {
+        *self.l
+    }
+
+    pub fn a(&self) -> f32 {
+        *self.a
+    }
+
+    pub fn b(&self) -> f32 {
+        *self.b
+    }
+}
+
+impl From<Cielab> for Din99o {
+    fn from(value: Cielab) -> Self {
+        let [l, a, b] = din99_from_lab(
+            value,
+            &Din99Params {
+                l_scale: 303.67,
+                l_log: 0.0039,
+                rotation: 26.0,
+                f_scale: 0.83,
+                c_log: 0.075,
+                c_scale: 0.0435,
+                hue_rotation: 26.0,
+            },
+        );
+        unsafe {
+            Self {
+                l: NotNan::new_unchecked(l),
+                a: NotNan::new_unchecked(a),
+                b: NotNan::new_unchecked(b),
+            }
+        }
+    }
+}
+
+impl From<Rgb> for Din99o {
+    fn from(value: Rgb) -> Self {
+        Self::from(Cielab::from(value))
+    }
+}
+
+impl core::fmt::Display for Din99o {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Din99o", [self.l, self.a, self.b])
+    }
+}
+
+/**
+    Constants of the DIN99 family of the color spaces, angles are in degrees.
+*/
+struct Din99Params {
+    l_scale: f32,
+    l_log: f32,
+    rotation: f32,
+    f_scale: f32,
+    c_log: f32,
+    c_scale: f32,
+    hue_rotation: f32,
+}
+
+fn din99_from_lab(lab: Cielab, params: &Din99Params) -> [f32; 3] {
+    let (sin, cos) = (
+        sinf(params.rotation.to_radians()),
+        cosf(params.rotation.to_radians()),
+    );
+    let e = *lab.a * cos + *lab.b * sin;
+    let f = params.f_scale * (*lab.b * cos - *lab.a * sin);
+
+    let chroma = log1pf(params.c_log * hypotf(e, f)) / params.c_scale;
+    let hue = atan2f(f, e) + params.hue_rotation.to_radians();
+    [
+        params.l_scale * log1pf(params.l_log * *lab.l),
+        chroma * cosf(hue),
+        chroma * sinf(hue),
+    ]
+}
+
+/**
+    Luminance of the SDR reference white in cd/m², used to place relative
+    colors into the absolute HDR color spaces.
+
+    Info: ITU-R BT.2408
+*/
+pub const SDR_WHITE_LUMINANCE: f32 = 203.0;
+
+/**
+    Color space for HDR and wide color gamut content with intensity `I` and
+    chroma components `Ct` and `Cp`.
+
+    Formula: ITU-R BT.2100
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ictcp {
+    pub(crate) i: NotNan<f32>,
+    pub(crate) ct: NotNan<f32>,
+    pub(crate) cp: NotNan<f32>,
+}
+
+impl Ictcp {
+    /**
+        Converts color from XYZ relative to D65 white with the absolute
+        luminance `Y` in cd/m².
+
+        # Example
+
+        ```
+        use codi_core::color_space::{Ictcp, Xyz};
+
+        let white = Xyz::new(95.05, 100.0, 108.88).unwrap();
+        let bright = Xyz::new(950.5, 1000.0, 1088.8).unwrap();
+        assert!(Ictcp::from_absolute(bright).i() > Ictcp::from_absolute(white).i());
+        ```
+    */
+    pub fn from_absolute(xyz: Xyz) -> Self {
+        // XYZ to BT.2020 and then to LMS.
+        const LMS_COEF: [[f32; 3]; 3] = [
+            [0.359_195_4, 0.697_642_5, -0.035_867_366],
+            [-0.192_124_72, 1.100_512_1, 0.075_401_63],
+            [0.007_077_108, 0.074_835_86, 0.843_500_8],
+        ];
+        const ICTCP_COEF: [[f32; 3]; 3] = [
+            [2048.0 / 4096.0, 2048.0 / 4096.0, 0.0],
+            [6610.0 / 4096.0, -13613.0 / 4096.0, 7003.0 / 4096.0],
+            [17933.0 / 4096.0, -17390.0 / 4096.0, -543.0 / 4096.0],
+        ];
+
+        let lms = absolute_lms(&LMS_COEF, [*xyz.x, *xyz.y, *xyz.z])
+            .map(|col| TransferFunction::Pq.encode(col));
+        let res = crate::math_utils::matrix_mul(&ICTCP_COEF, &lms.map(|col| [col]));
+        unsafe {
+            Self {
+                i: NotNan::new_unchecked(res[0][0]),
+                ct: NotNan::new_unchecked(res[1][0]),
+                cp: NotNan::new_unchecked(res[2][0]),
+            }
+        }
+    }
+
+    /// Intensity.
+    pub fn i(&self) -> f32 {
+        *self.i
+    }
+
+    /// Blue-yellow chroma.
+    pub fn ct(&self) -> f32 {
+        *self.ct
+    }
+
+    /// Red-green chroma.
+    pub fn cp(&self) -> f32 {
+        *self.cp
+    }
+}
+
+impl From<Xyz> for Ictcp {
+    /// White of the relative color has luminance of [`SDR_WHITE_LUMINANCE`].
+    fn from(value: Xyz) -> Self {
+        Self::from_absolute(value.scale(SDR_WHITE_LUMINANCE))
+    }
+}
+
+impl From<Rgb> for Ictcp {
+    fn from(value: Rgb) -> Self {
+        Xyz::from(value).into()
+    }
+}
+
+impl core::fmt::Display for Ictcp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Ictcp", [self.i, self.ct, self.cp])
+    }
+}
+
+/**
+    Jzazbz color space, perceptually uniform for both SDR and HDR content.
+
+    Paper: Safdar et al., Perceptually uniform color space for image signals
+    including high dynamic range and wide gamut, 2017.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Jzazbz {
+    pub(crate) jz: NotNan<f32>,
+    pub(crate) az: NotNan<f32>,
+    pub(crate) bz: NotNan<f32>,
+}
+
+impl Jzazbz {
+    /**
+        Converts color from XYZ relative to D65 white with the absolute
+        luminance `Y` in cd/m².
+    */
+    pub fn from_absolute(xyz: Xyz) -> Self {
+        const B: f32 = 1.15;
+        const G: f32 = 0.66;
+        const D: f32 = -0.56;
+        const D0: f32 = 1.629_55e-11;
+        const LMS_COEF: [[f32; 3]; 3] = [
+            [0.414_789_7, 0.579_999, 0.014_648],
+            [-0.201_51, 1.120_649, 0.053_100_8],
+            [-0.016_600_8, 0.2648, 0.668_479_9],
+        ];
+        const IAB_COEF: [[f32; 3]; 3] = [
+            [0.5, 0.5, 0.0],
+            [3.524, -4.066_708, 0.542_708],
+            [0.199_076, 1.096_799, -1.295_875],
+        ];
+
+        let (x, y, z) = (*xyz.x, *xyz.y, *xyz.z);
+        let xyz = [B * x - (B - 1.0) * z, G * y - (G - 1.0) * x, z];
+        let lms =
+            absolute_lms(&LMS_COEF, xyz).map(|col| pq_encode(col.abs(), 1.7 * PQ_M2).copysign(col));
+        let res = crate::math_utils::matrix_mul(&IAB_COEF, &lms.map(|col| [col]));
+        let iz = res[0][0];
+        unsafe {
+            Self {
+                jz: NotNan::new_unchecked((1.0 + D) * iz / (1.0 + D * iz) - D0),
+                az: NotNan::new_unchecked(res[1][0]),
+                bz: NotNan::new_unchecked(res[2][0]),
+            }
+        }
+    }
+
+    /// Lightness.
+    pub fn jz(&self) -> f32 {
+        *self.jz
+    }
+
+    /// Red-green axis.
+    pub fn az(&self) -> f32 {
+        *self.az
+    }
+
+    /// Yellow-blue axis.
+    pub fn bz(&self) -> f32 {
+        *self.bz
+    }
+}
+
+impl From<Xyz> for Jzazbz {
+    /// White of the relative color has luminance of [`SDR_WHITE_LUMINANCE`].
+    fn from(value: Xyz) -> Self {
+        Self::from_absolute(value.scale(SDR_WHITE_LUMINANCE))
+    }
+}
+
+impl From<Rgb> for Jzazbz {
+    fn from(value: Rgb) -> Self {
+        Xyz::from(value).into()
+    }
+}
+
+impl core::fmt::Display for Jzazbz {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Jzazbz", [self.jz, self.az, self.bz])
+    }
+}
+
+/**
+    Cone responses relative to the peak luminance of 10000 cd/m².
+*/
+fn absolute_lms(coef: &[[f32; 3]; 3], [x, y, z]: [f32; 3]) -> [f32; 3] {
+    const PEAK_LUMINANCE: f32 = 10_000.0;
+
+    let res = crate::math_utils::matrix_mul(coef, &[[x], [y], [z]]);
+    [res[0][0], res[1][0], res[2][0]].map(|col| col / PEAK_LUMINANCE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Oklab {
+    pub(crate) l: NotNan<f32>,
+    pub(crate) a: NotNan<f32>,
+    pub(crate) b: NotNan<f32>,
+}
+
+impl Oklab {
+    /**
+        Creates color from lightness in range [0, 1] and `a`, `b` axes.
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(l: f32, a: f32, b: f32) -> Result<Self> {
+        Ok(Self {
+            l: finite(l)?,
+            a: finite(a)?,
+            b: finite(b)?,
+        })
+    }
+
+    pub fn l(&self) -> f32 {
+        *self.l
+    }
+
+    pub fn a(&self) -> f32 {
+        *self.a
+    }
+
+    pub fn b(&self) -> f32 {
+        *self.b
+    }
+}
+
+impl Oklab {
+    /**
+        Converts linear sRGB into Oklab, channels may be out of range [0, 1].
+
+        <https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab>
+    */
+    pub(crate) fn from_linear_srgb([r, g, b]: [f32; 3]) -> Self {
+        const LMS_COEF: [[f32; 3]; 3] = [
+            [0.412_221_46, 0.536_332_55, 0.051_445_995],
+            [0.211_903_5, 0.680_699_5, 0.107_396_96],
+            [0.088_302_46, 0.281_718_85, 0.629_978_7],
+        ];
+        const LAB_COEF: [[f32; 3]; 3] = [
+            [0.210_454_26, 0.793_617_8, -0.004_072_047],
+            [1.977_998_5, -2.428_592_2, 0.450_593_7],
+            [0.025_904_037, 0.782_771_77, -0.808_675_77],
+        ];
+
+        let lin_col: [[f32; 1]; 3] = [[r], [g], [b]];
+        let lms = crate::math_utils::matrix_mul(&LMS_COEF, &lin_col);
+        let lms: [[f32; 1]; 3] = [[cbrtf(lms[0][0])], [cbrtf(lms[1][0])], [cbrtf(lms[2][0])]];
+        let res = crate::math_utils::matrix_mul(&LAB_COEF, &lms);
+
+        unsafe {
+            Self {
+                l: NotNan::new_unchecked(res[0][0]),
+                a: NotNan::new_unchecked(res[1][0]),
+                b: NotNan::new_unchecked(res[2][0]),
+            }
+        }
+    }
+
+    /**
+        Converts color into linear sRGB, channels of the colors outside of
+        the sRGB gamut are out of range [0, 1].
+
+        <https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab>
+    */
+    pub(crate) fn to_linear_srgb(self) -> [f32; 3] {
+        const LMS_COEF: [[f32; 3]; 3] = [
+            [1.0, 0.396_337_78, 0.215_803_76],
+            [1.0, -0.105_561_346, -0.063_854_17],
+            [1.0, -0.089_484_18, -1.291_485_5],
+        ];
+        const RGB_COEF: [[f32; 3]; 3] = [
+            [4.076_741_7, -3.307_711_6, 0.230_969_94],
+            [-1.268_438, 2.609_757_4, -0.341_319_38],
+            [-0.004_196_086_3, -0.703_418_6, 1.707_614_7],
+        ];
+
+        let lab: [[f32; 1]; 3] = [[*self.l], [*self.a], [*self.b]];
+        let lms = crate::math_utils::matrix_mul(&LMS_COEF, &lab);
+        let lms: [[f32; 1]; 3] = [
+            [powf(lms[0][0], 3.0)],
+            [powf(lms[1][0], 3.0)],
+            [powf(lms[2][0], 3.0)],
+        ];
+        let res = crate::math_utils::matrix_mul(&RGB_COEF, &lms);
+        [res[0][0], res[1][0], res[2][0]]
+    }
+}
+
+impl From<Rgb> for Oklab {
+    fn from(value: Rgb) -> Self {
+        Self::from_linear_srgb(value.to_linear())
+    }
+}
+
+impl From<Xyz> for Oklab {
+    fn from(value: Xyz) -> Self {
+        Self::from_linear_srgb(value.to_linear_srgb())
+    }
+}
+
+impl From<Cielab> for Oklab {
+    fn from(value: Cielab) -> Self {
+        Self::from(Xyz::from(value))
+    }
+}
+
+impl TryFrom<[f32; 3]> for Oklab {
+    type Error = Error;
+
+    fn try_from([l, a, b]: [f32; 3]) -> Result<Self> {
+        Self::new(l, a, b)
+    }
+}
+
+impl core::fmt::Display for Oklab {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Oklab", [self.l, self.a, self.b])
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(value: Oklch) -> Self {
+        let hue = value.h.to_radians();
+
+        unsafe {
+            Self {
+                l: value.l,
+                a: NotNan::new_unchecked(*value.c * cosf(hue)),
+                b: NotNan::new_unchecked(*value.c * sinf(hue)),
+            }
+        }
+    }
+}
+
+/**
+    Polar (cylindrical) form of [`Oklab`]. Hue is stored in degrees.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Oklch {
+    pub(crate) l: NotNan<f32>,
+    pub(crate) c: NotNan<f32>,
+    pub(crate) h: NotNan<f32>,
+}
+
+impl Oklch {
+    /**
+        Creates color from lightness in range [0, 1], chroma and hue
+        normalized into range [0, 360).
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(l: f32, c: f32, h: f32) -> Result<Self> {
+        Ok(Self {
+            l: finite(l)?,
+            c: finite(c)?,
+            h: hue(h)?,
+        })
+    }
+
+    pub fn l(&self) -> f32 {
+        *self.l
+    }
+
+    pub fn c(&self) -> f32 {
+        *self.c
+    }
+
+    pub fn h(&self) -> f32 {
+        *self.h
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(value: Oklab) -> Self {
+        unsafe {
+            Self {
+                l: value.l,
+                c: NotNan::new_unchecked(hypotf(*value.a, *value.b)),
+                h: NotNan::new_unchecked(crate::math_utils::hue_angle(*value.a, *value.b)),
+            }
+        }
+    }
+}
+
+impl From<Rgb> for Oklch {
+    fn from(value: Rgb) -> Self {
+        Oklab::from(value).into()
+    }
+}
+
+impl TryFrom<[f32; 3]> for Oklch {
+    type Error = Error;
+
+    fn try_from([l, c, h]: [f32; 3]) -> Result<Self> {
+        Self::new(l, c, h)
+    }
+}
+
+impl core::fmt::Display for Oklch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Oklch", [self.l, self.c, self.h])
+    }
+}
+
+/**
+    Hue in degrees in range [0, 360), maximum and minimum of gamma-corrected
+    channels in range [0, 1]. Hue of the achromatic colors is `0`.
+*/
+fn hue_max_min([r, g, b]: [f32; 3]) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+
+    let chroma = max - min;
+    #[allow(clippy::float_cmp)]
+    let sector = if max == min {
+        0.0
+    } else if max == r {
+        (g - b) / chroma
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    (rem_euclid(sector * 60.0, 360.0), max, min)
+}
+
+/**
+    Hue, saturation and lightness. Hue is in degrees in range [0, 360),
+    saturation and lightness are in range [0, 1].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hsl {
+    pub(crate) h: NotNan<f32>,
+    pub(crate) s: NotNan<f32>,
+    pub(crate) l: NotNan<f32>,
+}
+
+impl Hsl {
+    /**
+        Creates color with hue normalized into range [0, 360) and saturation
+        and lightness clamped into range [0, 1].
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(h: f32, s: f32, l: f32) -> Result<Self> {
+        Ok(Self {
+            h: hue(h)?,
+            s: unit(s)?,
+            l: unit(l)?,
+        })
+    }
+
+    pub fn h(&self) -> f32 {
+        *self.h
+    }
+
+    pub fn s(&self) -> f32 {
+        *self.s
+    }
+
+    pub fn l(&self) -> f32 {
+        *self.l
+    }
+
+    /**
+        Converts color into gamma-corrected sRGB channels in range [0, 1].
+
+        <https://www.w3.org/TR/css-color-4/#hsl-to-rgb>
+    */
+    pub(crate) fn to_srgb(self) -> [f32; 3] {
+        let (hue, sat, light) = (*self.h, *self.s, *self.l);
+        let func = |n: f32| {
+            let k = (n + hue / 30.0) % 12.0;
+            let a = sat * light.min(1.0 - light);
+            light - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        [func(0.0), func(8.0), func(4.0)]
+    }
+
+    /**
+        Converts gamma-corrected sRGB channels, values out of range [0, 1] are
+        clamped.
+
+        <https://en.wikipedia.org/wiki/HSL_and_HSV#From_RGB>
+    */
+    pub fn from_srgb(srgb: [f32; 3]) -> Self {
+        let (hue, max, min) = hue_max_min(srgb.map(|col| col.clamp(0.0, 1.0)));
+        let light = f32::midpoint(max, min);
+        let sat = if max > min {
+            (max - min) / (1.0 - (2.0 * light - 1.0).abs())
+        } else {
+            0.0
+        };
+
+        unsafe {
+            Self {
+                h: NotNan::new_unchecked(hue),
+                s: NotNan::new_unchecked(sat),
+                l: NotNan::new_unchecked(light),
+            }
+        }
+    }
+}
+
+impl From<Rgb> for Hsl {
+    fn from(value: Rgb) -> Self {
+        Self::from_srgb(value.to_unit())
+    }
+}
+
+impl TryFrom<[f32; 3]> for Hsl {
+    type Error = Error;
+
+    fn try_from([h, s, l]: [f32; 3]) -> Result<Self> {
+        Self::new(h, s, l)
+    }
+}
+
+impl core::fmt::Display for Hsl {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Hsl", [self.h, self.s, self.l])
+    }
+}
+
+/**
+    Hue, saturation and value, also known as HSB. Hue is in degrees in range
+    [0, 360), saturation and value are in range [0, 1].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hsv {
+    pub(crate) h: NotNan<f32>,
+    pub(crate) s: NotNan<f32>,
+    pub(crate) v: NotNan<f32>,
+}
+
+impl Hsv {
+    /**
+        Creates color with hue normalized into range [0, 360) and saturation
+        and value clamped into range [0, 1].
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(h: f32, s: f32, v: f32) -> Result<Self> {
+        Ok(Self {
+            h: hue(h)?,
+            s: unit(s)?,
+            v: unit(v)?,
+        })
+    }
+
+    pub fn h(&self) -> f32 {
+        *self.h
+    }
+
+    pub fn s(&self) -> f32 {
+        *self.s
+    }
+
+    pub fn v(&self) -> f32 {
+        *self.v
+    }
+
+    /**
+        Converts color into gamma-corrected sRGB channels in range [0, 1].
+
+        <https://en.wikipedia.org/wiki/HSL_and_HSV#HSV_to_RGB_alternative>
+    */
+    pub(crate) fn to_srgb(self) -> [f32; 3] {
+        let (hue, sat, val) = (*self.h, *self.s, *self.v);
+        let func = |n: f32| {
+            let k = (n + hue / 60.0) % 6.0;
+            val - val * sat * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        [func(5.0), func(3.0), func(1.0)]
+    }
+
+    /**
+        Converts gamma-corrected sRGB channels, values out of range [0, 1] are
+        clamped.
+
+        <https://en.wikipedia.org/wiki/HSL_and_HSV#From_RGB>
+    */
+    pub fn from_srgb(srgb: [f32; 3]) -> Self {
+        let (hue, max, min) = hue_max_min(srgb.map(|col| col.clamp(0.0, 1.0)));
+        let sat = if max > 0.0 { (max - min) / max } else { 0.0 };
+
+        unsafe {
+            Self {
+                h: NotNan::new_unchecked(hue),
+                s: NotNan::new_unchecked(sat),
+                v: NotNan::new_unchecked(max),
+            }
+        }
+    }
+}
+
+impl From<Rgb> for Hsv {
+    fn from(value: Rgb) -> Self {
+        Self::from_srgb(value.to_unit())
+    }
+}
+
+impl TryFrom<[f32; 3]> for Hsv {
+    type Error = Error;
+
+    fn try_from([h, s, v]: [f32; 3]) -> Result<Self> {
+        Self::new(h, s, v)
+    }
+}
+
+impl core::fmt::Display for Hsv {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Hsv", [self.h, self.s, self.v])
+    }
+}
+
+/**
+    Hue, whiteness and blackness. Hue is in degrees in range [0, 360),
+    whiteness and blackness are in range [0, 1].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hwb {
+    pub(crate) h: NotNan<f32>,
+    pub(crate) w: NotNan<f32>,
+    pub(crate) b: NotNan<f32>,
+}
+
+impl Hwb {
+    /**
+        Creates color with hue normalized into range [0, 360) and whiteness
+        and blackness clamped into range [0, 1].
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the values is NaN or
+        [`Error::NotFinite`] if any of them is infinite.
+    */
+    pub fn new(h: f32, w: f32, b: f32) -> Result<Self> {
+        Ok(Self {
+            h: hue(h)?,
+            w: unit(w)?,
+            b: unit(b)?,
+        })
+    }
+
+    pub fn h(&self) -> f32 {
+        *self.h
+    }
+
+    pub fn w(&self) -> f32 {
+        *self.w
+    }
+
+    pub fn b(&self) -> f32 {
+        *self.b
+    }
+
+    /**
+        Converts color into gamma-corrected sRGB channels in range [0, 1].
+
+        <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+    */
+    pub(crate) fn to_srgb(self) -> [f32; 3] {
+        let (white, black) = (*self.w, *self.b);
+        if white + black >= 1.0 {
+            let gray = white / (white + black);
+            return [gray; 3];
+        }
+        let sat_value = 1.0 - black;
+        Hsv {
+            h: self.h,
+            s: unsafe { NotNan::new_unchecked(1.0 - white / sat_value) },
+            v: unsafe { NotNan::new_unchecked(sat_value) },
+        }
+        .to_srgb()
+    }
+
+    /**
+        Converts gamma-corrected sRGB channels, values out of range [0, 1] are
+        clamped.
+
+        <https://www.w3.org/TR/css-color-4/#rgb-to-hwb>
+    */
+    pub fn from_srgb(srgb: [f32; 3]) -> Self {
+        let (hue, max, min) = hue_max_min(srgb.map(|col| col.clamp(0.0, 1.0)));
+
+        unsafe {
+            Self {
+                h: NotNan::new_unchecked(hue),
+                w: NotNan::new_unchecked(min),
+                b: NotNan::new_unchecked(1.0 - max),
+            }
+        }
+    }
+}
+
+impl From<Rgb> for Hwb {
+    fn from(value: Rgb) -> Self {
+        Self::from_srgb(value.to_unit())
+    }
+}
+
+impl TryFrom<[f32; 3]> for Hwb {
+    type Error = Error;
+
+    fn try_from([h, w, b]: [f32; 3]) -> Result<Self> {
+        Self::new(h, w, b)
+    }
+}
+
+impl core::fmt::Display for Hwb {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_components(f, "Hwb", [self.h, self.w, self.b])
+    }
+}
+
+/**
+    Writes `name(c1, c2, c3)` passing formatting options to the components.
+*/
+pub(crate) fn fmt_components(
+    f: &mut core::fmt::Formatter<'_>,
+    name: &str,
+    components: [NotNan<f32>; 3],
+) -> core::fmt::Result {
+    write!(f, "{name}(")?;
+    for (idx, component) in components.iter().enumerate() {
+        if idx > 0 {
+            write!(f, ", ")?;
+        }
+        core::fmt::Display::fmt(component, f)?;
+    }
+    write!(f, ")")
+}
+
+fn hue(value: f32) -> Result<NotNan<f32>> {
+    finite(value).and_then(|value| finite(rem_euclid(*value, 360.0)))
+}
+
+fn unit(value: f32) -> Result<NotNan<f32>> {
+    finite(value).map(|value| value.clamp(NotNan::default(), NotNan::from(1u8)))
+}
+
+pub(crate) fn finite(value: f32) -> Result<NotNan<f32>> {
+    if value.is_infinite() {
+        return Err(Error::NotFinite);
+    }
+    NotNan::new(value).map_err(|_| Error::NotANumber)
+}
+
+const fn u8_from_two_hex(hex1: u8, hex2: u8) -> Result<u8> {
+    match (hex_byte_to_dec(hex1), hex_byte_to_dec(hex2)) {
+        (Ok(v1), Ok(v2)) => Ok(v1 * 16 + v2),
+        (Err(err), _) | (_, Err(err)) => Err(err),
+    }
+}
+
+/**
+    Converts ascii hex digit to the decimal number.
+*/
+pub(crate) const fn hex_byte_to_dec(hex: u8) -> Result<u8> {
+    match hex {
+        b'0'..=b'9' => Ok(hex - b'0'),
+        b'a'..=b'f' => Ok(hex - b'a' + 10),
+        b'A'..=b'F' => Ok(hex - b'A' + 10),
+        _ => Err(Error::NotAsciiHexDigit(hex)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Check with and without "#" prefix, lowercase and uppercase. The behavior
+    // should be the same.
+    #[test]
+    fn rgb_from_hex_some() {
+        let tests = [
+            ("#000000", rgb(0, 0, 0)),
+            ("#FFfFff", rgb(255, 255, 255)),
+            ("#010100", rgb(1, 1, 0)),
+            ("#010a00", rgb(1, 10, 0)),
+            ("#00fF00", rgb(0, 255, 0)),
+            ("#abcdef", rgb(171, 205, 239)),
+        ];
+        for (case, expected) in tests {
+            from_hex_assert_ok(case, expected);
+            from_hex_assert_ok(&case.to_ascii_lowercase(), expected);
+            from_hex_assert_ok(&case.to_ascii_uppercase(), expected);
+            from_hex_assert_ok(&case[1..].to_ascii_lowercase(), expected);
+            from_hex_assert_ok(&case[1..].to_ascii_uppercase(), expected);
+        }
+    }
+
+    fn from_hex_assert_ok(input: &str, expected: Rgb) {
+        assert_eq!(
+            Rgb::try_from(input.as_bytes()),
+            Ok(expected),
+            "input: {input}"
+        );
+    }
+
+    #[test]
+    fn rgb_from_str() {
+        assert_eq!("#FFfFff".parse(), Ok(rgb(255, 255, 255)));
+        assert_eq!("abcdef".parse(), Ok(rgb(171, 205, 239)));
+        assert_eq!(" #abc ".parse(), Ok(rgb(170, 187, 204)));
+        assert_eq!("hsl(120deg 100% 25%)".parse(), Ok(rgb(0, 128, 0)));
+        assert_eq!(Rgb::try_from("Fuchsia"), Ok(rgb(255, 0, 255)));
+
+        let err = Rgb::try_from("#00000g").unwrap_err();
+        assert_eq!(err.offset, 6);
+        assert_eq!(err.kind, crate::css::ErrorKind::NotAsciiHexDigit(b'g'));
+        assert!("unknown".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn rgb_from_hex_error() {
+        let tests = [
+            "0000000", "#00000", "#00000g", "-000000", "#123 45", "123456 ",
+        ];
+        for test in tests {
+            assert!(Rgb::try_from(test.as_bytes()).is_err(), "input: {test}");
+        }
+    }
+
+    #[test]
+    fn hex_byte_to_dec_ok() {
+        assert_eq!(hex_byte_to_dec(b'0'), Ok(0));
         assert_eq!(hex_byte_to_dec(b'1'), Ok(1));
         assert_eq!(hex_byte_to_dec(b'9'), Ok(9));
         assert_eq!(hex_byte_to_dec(b'a'), Ok(10));
@@ -406,4 +1554,218 @@ mod test {
             assert_eq!(color, Rgb::from(Cielab::from(color)), "input: {color}");
         });
     }
+
+    #[test]
+    #[ignore = "slow test"]
+    fn rgb_to_oklab_to_rgb() {
+        Rgb::for_each(|color: Rgb| {
+            assert_eq!(color, Rgb::from(Oklab::from(color)), "input: {color}");
+        });
+    }
+
+    #[test]
+    #[ignore = "slow test"]
+    fn rgb_to_oklch_to_rgb() {
+        Rgb::for_each(|color: Rgb| {
+            assert_eq!(color, Rgb::from(Oklch::from(color)), "input: {color}");
+        });
+    }
+
+    #[test]
+    fn oklab_reference_values() {
+        // https://bottosson.github.io/posts/oklab/#table-of-example-xyz-and-oklab-pairs
+        // and CSS Color 4 sample values for sRGB primaries.
+        let tests = [
+            (rgb(255, 255, 255), (1.0, 0.0, 0.0)),
+            (rgb(255, 0, 0), (0.627_955, 0.224_863, 0.125_846)),
+            (rgb(0, 255, 0), (0.866_440, -0.233_888, 0.179_498)),
+            (rgb(0, 0, 255), (0.452_014, -0.032_457, -0.311_528)),
+        ];
+        for (color, (l, a, b)) in tests {
+            let lab = Oklab::from(color);
+            assert!(
+                (*lab.l - l).abs() < 1e-3 && (*lab.a - a).abs() < 1e-3 && (*lab.b - b).abs() < 1e-3,
+                "input: {color}, got: {lab:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn xyz_and_cielab_constructors() {
+        let lab = Cielab::new(53.24, 80.09, 67.2).unwrap();
+        assert_eq!((lab.l(), lab.a(), lab.b()), (53.24, 80.09, 67.2));
+        assert_eq!(Cielab::try_from([53.24, 80.09, 67.2]), Ok(lab));
+        assert_eq!(Rgb::from(lab), rgb(255, 0, 0));
+
+        let xyz = Xyz::new(0.950_489, 1.0, 1.088_84).unwrap();
+        assert_eq!((xyz.x(), xyz.y(), xyz.z()), (0.950_489, 1.0, 1.088_84));
+        assert_eq!(Xyz::try_from([0.950_489, 1.0, 1.088_84]), Ok(xyz));
+        assert_eq!(Rgb::from(xyz), rgb(255, 255, 255));
+
+        assert_eq!(Cielab::new(50.0, f32::NAN, 0.0), Err(Error::NotANumber));
+        assert_eq!(Xyz::try_from([0.0, 0.0, f32::NAN]), Err(Error::NotANumber));
+        assert_eq!(Cielab::new(f32::INFINITY, 0.0, 0.0), Err(Error::NotFinite));
+        assert_eq!(Xyz::new(0.0, f32::NEG_INFINITY, 0.0), Err(Error::NotFinite));
+    }
+
+    #[test]
+    fn xyz_and_cielab_display() {
+        let lab = Cielab::new(53.24, -80.5, 0.0).unwrap();
+        assert_eq!(lab.to_string(), "Cielab(53.24, -80.5, 0)");
+        assert_eq!(format!("{lab:.1}"), "Cielab(53.2, -80.5, 0.0)");
+
+        let xyz = Xyz::new(0.5, 1.0, 0.25).unwrap();
+        assert_eq!(xyz.to_string(), "Xyz(0.5, 1, 0.25)");
+        assert_eq!(format!("{xyz:.2}"), "Xyz(0.50, 1.00, 0.25)");
+    }
+
+    #[test]
+    fn hdr_reference_values() {
+        // Test data from colour-science.
+        let jzazbz =
+            Jzazbz::from_absolute(Xyz::new(0.206_540_08, 0.121_972_25, 0.051_369_52).unwrap());
+        assert!((jzazbz.jz() - 0.005_350_48).abs() < 1e-6, "{jzazbz}");
+        assert!((jzazbz.az() - 0.009_243_02).abs() < 1e-6, "{jzazbz}");
+        assert!((jzazbz.bz() - 0.005_260_07).abs() < 1e-6, "{jzazbz}");
+
+        // Peak white of PQ has the maximum intensity.
+        let peak = Ictcp::from_absolute(Illuminant::D65.white_point().scale(10_000.0));
+        assert!((peak.i() - 1.0).abs() < 1e-4 && peak.ct().abs() < 1e-4 && peak.cp().abs() < 1e-4);
+
+        let white = Rgb::new(255, 255, 255);
+        let (ictcp, jzazbz) = (Ictcp::from(white), Jzazbz::from(white));
+        assert!((ictcp.i() - 0.580_7).abs() < 1e-3, "{ictcp}");
+        assert!(
+            ictcp.ct().abs() < 1e-3 && ictcp.cp().abs() < 1e-3,
+            "{ictcp}"
+        );
+        assert!((jzazbz.jz() - 0.222_1).abs() < 1e-3, "{jzazbz}");
+        assert!(
+            jzazbz.az().abs() < 1e-3 && jzazbz.bz().abs() < 1e-3,
+            "{jzazbz}"
+        );
+    }
+
+    #[test]
+    fn din99_reference_values() {
+        // Test data from colour-science.
+        let lab = Cielab::new(41.527_875, 52.638_583, 26.923_18).unwrap();
+        let din99 = Din99::from(lab);
+        let expected = [53.228_22, 28.416_346, 3.898_396];
+        for (actual, expected) in [din99.l(), din99.a(), din99.b()].into_iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-3, "{din99}");
+        }
+
+        // Achromatic colors stay on the lightness axis, white has lightness 100.
+        for color in [rgb(255, 255, 255), rgb(0, 0, 0), rgb(128, 128, 128)] {
+            let (din99, din99o) = (Din99::from(color), Din99o::from(color));
+            assert!(din99.a().abs() < 0.05 && din99.b().abs() < 0.05, "{din99}");
+            assert!(
+                din99o.a().abs() < 0.05 && din99o.b().abs() < 0.05,
+                "{din99o}"
+            );
+        }
+        assert!((Din99::from(rgb(255, 255, 255)).l() - 100.0).abs() < 1e-2);
+        assert!((Din99o::from(rgb(255, 255, 255)).l() - 100.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn oklab_and_oklch_constructors() {
+        let lab = Oklab::new(0.628, 0.225, 0.126).unwrap();
+        assert_eq!(Oklab::try_from([0.628, 0.225, 0.126]), Ok(lab));
+        assert_eq!(Rgb::from(lab), rgb(255, 0, 0));
+        assert_eq!(format!("{lab:.1}"), "Oklab(0.6, 0.2, 0.1)");
+
+        let lch = Oklch::new(0.452, 0.313, -95.95).unwrap();
+        assert!((lch.h() - 264.05).abs() < 1e-3);
+        assert_eq!(Rgb::from(lch), rgb(0, 0, 255));
+        assert_eq!(format!("{lch:.2}"), "Oklch(0.45, 0.31, 264.05)");
+
+        assert_eq!(Oklab::new(f32::NAN, 0.0, 0.0), Err(Error::NotANumber));
+        assert_eq!(
+            Oklch::try_from([0.5, 0.1, f32::INFINITY]),
+            Err(Error::NotFinite)
+        );
+        assert_eq!(Oklab::new(0.5, f32::INFINITY, 0.0), Err(Error::NotFinite));
+        assert_eq!(Oklch::new(0.5, f32::INFINITY, 10.0), Err(Error::NotFinite));
+    }
+
+    #[test]
+    #[ignore = "slow test"]
+    fn rgb_to_hsl_to_rgb() {
+        Rgb::for_each(|color: Rgb| {
+            assert_eq!(color, Rgb::from(Hsl::from(color)), "input: {color}");
+        });
+    }
+
+    #[test]
+    #[ignore = "slow test"]
+    fn rgb_to_hsv_to_rgb() {
+        Rgb::for_each(|color: Rgb| {
+            assert_eq!(color, Rgb::from(Hsv::from(color)), "input: {color}");
+        });
+    }
+
+    #[test]
+    #[ignore = "slow test"]
+    fn rgb_to_hwb_to_rgb() {
+        Rgb::for_each(|color: Rgb| {
+            assert_eq!(color, Rgb::from(Hwb::from(color)), "input: {color}");
+        });
+    }
+
+    #[test]
+    fn hue_based_reference_values() {
+        #[rustfmt::skip]
+        let tests = [
+            (rgb(255, 0, 0), [0.0, 1.0, 0.5], [0.0, 1.0, 1.0], [0.0, 0.0, 0.0]),
+            (rgb(0, 255, 0), [120.0, 1.0, 0.5], [120.0, 1.0, 1.0], [120.0, 0.0, 0.0]),
+            (rgb(0, 0, 255), [240.0, 1.0, 0.5], [240.0, 1.0, 1.0], [240.0, 0.0, 0.0]),
+            (rgb(255, 0, 255), [300.0, 1.0, 0.5], [300.0, 1.0, 1.0], [300.0, 0.0, 0.0]),
+            (rgb(255, 255, 255), [0.0, 0.0, 1.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+            (rgb(0, 0, 0), [0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+            (rgb(255, 127, 80), [16.114, 1.0, 0.656_86], [16.114, 0.686_27, 1.0], [16.114, 0.313_73, 0.0]),
+        ];
+        let assert_close = |actual: [f32; 3], expected: [f32; 3], color: Rgb| {
+            assert!(
+                actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(a, e)| (a - e).abs() < 1e-3),
+                "input: {color}, got: {actual:?}, expected: {expected:?}"
+            );
+        };
+        for (color, hsl, hsv, hwb) in tests {
+            let (h_s_l, h_s_v, h_w_b) = (Hsl::from(color), Hsv::from(color), Hwb::from(color));
+            assert_close([h_s_l.h(), h_s_l.s(), h_s_l.l()], hsl, color);
+            assert_close([h_s_v.h(), h_s_v.s(), h_s_v.v()], hsv, color);
+            assert_close([h_w_b.h(), h_w_b.w(), h_w_b.b()], hwb, color);
+
+            assert_eq!(Rgb::from(Hsl::try_from(hsl).unwrap()), color);
+            assert_eq!(Rgb::from(Hsv::try_from(hsv).unwrap()), color);
+            assert_eq!(Rgb::from(Hwb::try_from(hwb).unwrap()), color);
+        }
+    }
+
+    #[test]
+    fn hue_based_normalization() {
+        let hsl = Hsl::new(-30.0, 1.5, -0.5).unwrap();
+        assert_eq!((hsl.h(), hsl.s(), hsl.l()), (330.0, 1.0, 0.0));
+        assert!(Hsv::new(720.0, 0.5, 0.5).unwrap().h().abs() < 1e-6);
+        assert!(Hwb::new(360.0, 0.0, 0.0).unwrap().h().abs() < 1e-6);
+        assert_eq!(Hsl::new(f32::NAN, 0.0, 0.0), Err(Error::NotANumber));
+        assert_eq!(Hsv::new(f32::INFINITY, 0.0, 0.0), Err(Error::NotFinite));
+        assert_eq!(Hsl::new(0.0, f32::INFINITY, 0.5), Err(Error::NotFinite));
+        assert_eq!(Hwb::new(0.0, 0.0, f32::NAN), Err(Error::NotANumber));
+
+        // Whiteness and blackness that sum to more than 1 give gray.
+        assert_eq!(
+            Rgb::from(Hwb::new(0.0, 0.6, 0.6).unwrap()),
+            rgb(128, 128, 128)
+        );
+        assert_eq!(
+            format!("{:.1}", Hsl::new(90.0, 0.5, 0.3).unwrap()),
+            "Hsl(90.0, 0.5, 0.3)"
+        );
+    }
 }
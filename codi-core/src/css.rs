@@ -0,0 +1,871 @@
+use libm::{cosf, sinf};
+use ordered_float::NotNan;
+
+use crate::color_space::{Cielab, Hsl, Hwb, Oklab, Rgb, Xyz, hex_byte_to_dec, rgb};
+use crate::gamut::{GamutMapping, Mapped};
+use crate::html_color::COLORS;
+use crate::illuminant::{Adaptation, Illuminant};
+use crate::math_utils::rem_euclid;
+use crate::rgb_space::RgbSpace;
+
+/**
+    Named colors that are not in [`COLORS`], because they are duplicates.
+*/
+const EXTRA_NAMED_COLORS: [(&str, Rgb); 10] = [
+    ("aqua", rgb(0, 255, 255)),
+    ("fuchsia", rgb(255, 0, 255)),
+    ("darkgray", rgb(169, 169, 169)),
+    ("darkslategray", rgb(47, 79, 79)),
+    ("dimgray", rgb(105, 105, 105)),
+    ("gray", rgb(128, 128, 128)),
+    ("lightgray", rgb(211, 211, 211)),
+    ("lightslategray", rgb(119, 136, 153)),
+    ("slategray", rgb(112, 128, 144)),
+    ("rebeccapurple", rgb(102, 51, 153)),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Empty,
+    UnknownName,
+    HexWrongLen(usize),
+    NotAsciiHexDigit(u8),
+    UnknownFunction,
+    UnknownColorSpace,
+    UnexpectedChar(char),
+    /// Number, percentage, angle or `none` is expected.
+    ExpectedValue,
+    /// Value has type or unit that is not allowed in this position.
+    InvalidValue,
+    ExpectedCloseParen,
+    TrailingCharacters,
+}
+
+/**
+    Parse error with the byte offset in the input string where it occurred.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Error {
+    pub offset: usize,
+    pub kind: ErrorKind,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::result::Result<(), core::fmt::Error> {
+        match self.kind {
+            ErrorKind::Empty => write!(f, "color is empty"),
+            ErrorKind::UnknownName => write!(f, "unknown color name"),
+            ErrorKind::HexWrongLen(len) => {
+                write!(f, "hex color must have 3, 4, 6 or 8 digits, got {len}")
+            }
+            ErrorKind::NotAsciiHexDigit(dig) => {
+                write!(f, "{dig} is not ascii hexadecimal digit")
+            }
+            ErrorKind::UnknownFunction => write!(f, "unknown color function"),
+            ErrorKind::UnknownColorSpace => write!(f, "unsupported color space"),
+            ErrorKind::UnexpectedChar(ch) => write!(f, "unexpected character '{ch}'"),
+            ErrorKind::ExpectedValue => write!(f, "expected number, percentage or angle"),
+            ErrorKind::InvalidValue => write!(f, "value type is not allowed here"),
+            ErrorKind::ExpectedCloseParen => write!(f, "expected ')'"),
+            ErrorKind::TrailingCharacters => write!(f, "unexpected characters after color"),
+        }?;
+        write!(f, " at byte {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/**
+    Parses any CSS color: hex, named color or one of the color functions.
+
+    Spec: <https://www.w3.org/TR/css-color-4/>
+
+    Hex colors can be written without leading '#' too. Alpha channel is
+    validated, but ignored, because [`Rgb`] is opaque. Colors outside of the
+    sRGB gamut are mapped with [`GamutMapping::Css`], see [`parse_mapped`]
+    to choose other strategy.
+
+    # Errors
+
+    Returns [`Err`] with the byte offset of the invalid part.
+
+    # Example
+
+    ```
+    use codi_core::color_space::Rgb;
+    use codi_core::css::parse;
+
+    assert_eq!(parse("#0af"), Ok(Rgb::new(0, 170, 255)));
+    assert_eq!(parse("rgb(0 170 255 / 50%)"), Ok(Rgb::new(0, 170, 255)));
+    assert_eq!(parse("hsl(120deg, 100%, 25%)"), Ok(Rgb::new(0, 128, 0)));
+    assert_eq!(parse("RebeccaPurple"), Ok(Rgb::new(102, 51, 153)));
+    assert_eq!(parse("rgb(0 0)").unwrap_err().offset, 7);
+    ```
+*/
+pub fn parse(input: &str) -> Result<Rgb> {
+    parse_mapped(input, GamutMapping::default()).map(|mapped| mapped.color)
+}
+
+/**
+    Parses any CSS color like [`parse`] and maps colors outside of the sRGB
+    gamut with given strategy. Channels of `rgb()`, `hsl()` and `hwb()` are
+    clamped when parsed as the spec requires, so they are never reported as
+    clamped.
+
+    # Errors
+
+    Returns [`Err`] with the byte offset of the invalid part.
+
+    # Example
+
+    ```
+    use codi_core::color_space::Rgb;
+    use codi_core::css::parse_mapped;
+    use codi_core::gamut::GamutMapping;
+
+    let color = parse_mapped("color(display-p3 0 1 0)", GamutMapping::Clip).unwrap();
+    assert_eq!(color.color, Rgb::new(0, 255, 0));
+    assert!(color.clamped);
+    ```
+*/
+pub fn parse_mapped(input: &str, mapping: GamutMapping) -> Result<Mapped> {
+    let start = input.len() - input.trim_start().len();
+    let value = input.trim();
+    let err = |offset, kind| Error { offset, kind };
+
+    if value.is_empty() {
+        return Err(err(input.len(), ErrorKind::Empty));
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex, start + 1).map(Mapped::from);
+    }
+    if let Some(paren) = value.find('(') {
+        let mut parser = Parser {
+            input,
+            pos: start + paren + 1,
+            mapping,
+        };
+        let color = parser.function(&value[..paren], start)?;
+        parser.skip_whitespace();
+        if parser.pos < start + value.len() {
+            return Err(err(parser.pos, ErrorKind::TrailingCharacters));
+        }
+        return Ok(color);
+    }
+    if let Some(color) = find_named(value) {
+        return Ok(Mapped::from(color));
+    }
+    if value.bytes().all(|ch| ch.is_ascii_hexdigit()) {
+        return parse_hex(value, start).map(Mapped::from);
+    }
+    Err(err(start, ErrorKind::UnknownName))
+}
+
+fn find_named(name: &str) -> Option<Rgb> {
+    COLORS
+        .iter()
+        .map(|color| (color.name, color.color))
+        .chain(EXTRA_NAMED_COLORS)
+        .find(|(other, _)| other.eq_ignore_ascii_case(name))
+        .map(|(_, color)| color)
+}
+
+/**
+    Parses hex digits after '#', `offset` is position of the first digit.
+*/
+fn parse_hex(hex: &str, offset: usize) -> Result<Rgb> {
+    let mut digits = [0u8; 8];
+    for (idx, ch) in hex.bytes().enumerate() {
+        let dig = hex_byte_to_dec(ch).map_err(|_| Error {
+            offset: offset + idx,
+            kind: ErrorKind::NotAsciiHexDigit(ch),
+        })?;
+        if let Some(slot) = digits.get_mut(idx) {
+            *slot = dig;
+        }
+    }
+
+    match hex.len() {
+        // Alpha is ignored.
+        3 | 4 => Ok(Rgb::new(digits[0] * 17, digits[1] * 17, digits[2] * 17)),
+        6 | 8 => Ok(Rgb::new(
+            digits[0] * 16 + digits[1],
+            digits[2] * 16 + digits[3],
+            digits[4] * 16 + digits[5],
+        )),
+        len => Err(Error {
+            offset,
+            kind: ErrorKind::HexWrongLen(len),
+        }),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(f32),
+    Percentage(f32),
+    Dimension(f32, &'a str),
+    Ident(&'a str),
+    Comma,
+    Slash,
+    CloseParen,
+}
+
+/**
+    Value with its byte offset.
+*/
+#[derive(Debug, Clone, Copy)]
+struct Arg<'a> {
+    offset: usize,
+    token: Token<'a>,
+}
+
+impl Arg<'_> {
+    const fn error(&self, kind: ErrorKind) -> Error {
+        Error {
+            offset: self.offset,
+            kind,
+        }
+    }
+
+    /**
+        Number or percentage, where `percent_ref` is value of 100%.
+    */
+    fn number(&self, percent_ref: f32) -> Result<f32> {
+        let num = match self.token {
+            Token::Number(num) => num,
+            Token::Percentage(num) => num / 100.0 * percent_ref,
+            Token::Ident(ident) if ident.eq_ignore_ascii_case("none") => 0.0,
+            _ => return Err(self.error(ErrorKind::InvalidValue)),
+        };
+        self.finite(num)
+    }
+
+    /**
+        Hue in degrees.
+    */
+    fn hue(&self) -> Result<f32> {
+        let hue = match self.token {
+            Token::Number(num) => num,
+            Token::Dimension(num, unit) => {
+                let is = |name: &str| unit.eq_ignore_ascii_case(name);
+                if is("deg") {
+                    num
+                } else if is("grad") {
+                    num * 0.9
+                } else if is("rad") {
+                    num.to_degrees()
+                } else if is("turn") {
+                    num * 360.0
+                } else {
+                    return Err(self.error(ErrorKind::InvalidValue));
+                }
+            }
+            Token::Ident(ident) if ident.eq_ignore_ascii_case("none") => 0.0,
+            _ => return Err(self.error(ErrorKind::InvalidValue)),
+        };
+        self.finite(hue).map(|hue| rem_euclid(hue, 360.0))
+    }
+
+    /**
+        Numbers that overflow `f32` are parsed as infinity.
+    */
+    const fn finite(&self, num: f32) -> Result<f32> {
+        if num.is_finite() {
+            Ok(num)
+        } else {
+            Err(self.error(ErrorKind::InvalidValue))
+        }
+    }
+}
+
+/**
+    Three color channels and optional alpha.
+*/
+struct Args<'a> {
+    channels: [Arg<'a>; 3],
+    alpha: Option<Arg<'a>>,
+    /// Whether arguments are comma-separated.
+    legacy: bool,
+}
+
+impl Args<'_> {
+    fn validate_alpha(&self) -> Result<()> {
+        self.alpha
+            .map_or(Ok(()), |alpha| alpha.number(1.0).map(drop))
+    }
+
+    /**
+        Legacy syntax doesn't allow `none`, channels of `rgb()` must be all
+        numbers or all percentages and `hsl()` requires percentages for
+        saturation and lightness.
+    */
+    fn validate_legacy(&self, is_rgb: bool) -> Result<()> {
+        if !self.legacy {
+            return Ok(());
+        }
+        if let Some(arg) = self
+            .channels
+            .iter()
+            .chain(&self.alpha)
+            .find(|arg| matches!(arg.token, Token::Ident(_)))
+        {
+            return Err(arg.error(ErrorKind::InvalidValue));
+        }
+
+        let [c1, c2, c3] = &self.channels;
+        let is_percentage = |arg: &Arg| matches!(arg.token, Token::Percentage(_));
+        let invalid = if is_rgb {
+            [c2, c3]
+                .into_iter()
+                .find(|arg| is_percentage(arg) != is_percentage(c1))
+        } else {
+            [c2, c3].into_iter().find(|arg| !is_percentage(arg))
+        };
+        invalid.map_or(Ok(()), |arg| Err(arg.error(ErrorKind::InvalidValue)))
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    mapping: GamutMapping,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    const fn error(&self, kind: ErrorKind) -> Error {
+        Error {
+            offset: self.pos,
+            kind,
+        }
+    }
+
+    fn function(&mut self, name: &str, name_offset: usize) -> Result<Mapped> {
+        let is = |other: &str| name.eq_ignore_ascii_case(other);
+
+        if is("color") {
+            return self.color_fn();
+        }
+
+        let args = self.args(is("rgb") || is("rgba") || is("hsl") || is("hsla"))?;
+        args.validate_alpha()?;
+        args.validate_legacy(is("rgb") || is("rgba"))?;
+        let [c1, c2, c3] = args.channels;
+
+        let color = if is("rgb") || is("rgba") {
+            Mapped::from_srgb([
+                c1.number(255.0)? / 255.0,
+                c2.number(255.0)? / 255.0,
+                c3.number(255.0)? / 255.0,
+            ])
+        } else if is("hsl") || is("hsla") {
+            Mapped::from_srgb(hsl_to_srgb([
+                c1.hue()?,
+                c2.number(100.0)? / 100.0,
+                c3.number(100.0)? / 100.0,
+            ]))
+        } else if is("hwb") {
+            Mapped::from_srgb(hwb_to_srgb([
+                c1.hue()?,
+                c2.number(100.0)? / 100.0,
+                c3.number(100.0)? / 100.0,
+            ]))
+        } else if is("lab") {
+            let lab = [c1.number(100.0)?, c2.number(125.0)?, c3.number(125.0)?];
+            let channels = [(c1, lab[0] / 100.0), (c2, lab[1]), (c3, lab[2])];
+            self.map_linear(lab_d50_to_linear(lab), channels)?
+        } else if is("lch") {
+            let (chroma, hue) = (c2.number(150.0)?.max(0.0), c3.hue()?.to_radians());
+            let lab = [c1.number(100.0)?, chroma * cosf(hue), chroma * sinf(hue)];
+            let channels = [(c1, lab[0] / 100.0), (c2, chroma), (c3, 0.0)];
+            self.map_linear(lab_d50_to_linear(lab), channels)?
+        } else if is("oklab") {
+            let lab = [c1.number(1.0)?, c2.number(0.4)?, c3.number(0.4)?];
+            let channels = [(c1, lab[0]), (c2, lab[1]), (c3, lab[2])];
+            self.map_linear(oklab_to_linear(lab), channels)?
+        } else if is("oklch") {
+            let (chroma, hue) = (c2.number(0.4)?.max(0.0), c3.hue()?.to_radians());
+            let lab = [c1.number(1.0)?, chroma * cosf(hue), chroma * sinf(hue)];
+            let channels = [(c1, lab[0]), (c2, chroma), (c3, 0.0)];
+            self.map_linear(oklab_to_linear(lab), channels)?
+        } else {
+            return Err(Error {
+                offset: name_offset,
+                kind: ErrorKind::UnknownFunction,
+            });
+        };
+        Ok(color)
+    }
+
+    /**
+        Parses `color(<colorspace> c1 c2 c3 [/ alpha])`.
+    */
+    fn color_fn(&mut self) -> Result<Mapped> {
+        let space = self.next_value()?;
+        let Token::Ident(name) = space.token else {
+            return Err(space.error(ErrorKind::UnknownColorSpace));
+        };
+        let Some(rgb_space) = RgbSpace::find(name) else {
+            return Err(space.error(ErrorKind::UnknownColorSpace));
+        };
+
+        let args = self.args(false)?;
+        args.validate_alpha()?;
+        let [c1, c2, c3] = args.channels;
+        let channels = [c1.number(1.0)?, c2.number(1.0)?, c3.number(1.0)?];
+
+        let linear = if rgb_space == RgbSpace::SRGB || rgb_space == RgbSpace::SRGB_LINEAR {
+            channels.map(|col| rgb_space.transfer().decode(col))
+        } else {
+            // Error is reported below for the channel that caused it.
+            rgb_space
+                .to_xyz(channels)
+                .map_or([f32::NAN; 3], Xyz::to_linear_srgb)
+        };
+        let [v1, v2, v3] = channels;
+        self.map_linear(linear, [(c1, v1), (c2, v2), (c3, v3)])
+    }
+
+    /**
+        Maps converted color into the sRGB gamut. Large channels can overflow
+        during the conversion, the error is reported for the channel with the
+        greatest magnitude relative to its range.
+    */
+    fn map_linear(&self, linear: [f32; 3], channels: [(Arg, f32); 3]) -> Result<Mapped> {
+        if linear.iter().all(|col| col.is_finite()) {
+            return Ok(self.mapping.map_linear(linear));
+        }
+        let (arg, _) = channels
+            .into_iter()
+            .max_by(|(_, v1), (_, v2)| v1.abs().total_cmp(&v2.abs()))
+            .expect("SAFETY: there are always 3 channels");
+        Err(arg.error(ErrorKind::InvalidValue))
+    }
+
+    /**
+        Parses arguments till closing parenthesis. Supports both modern
+        space-separated syntax and legacy comma-separated syntax if
+        `legacy` is `true`.
+    */
+    fn args(&mut self, legacy: bool) -> Result<Args<'a>> {
+        let first = self.next_value()?;
+        let separator = self.next_token()?;
+
+        if legacy && separator.token == Token::Comma {
+            let second = self.next_value()?;
+            self.expect(Token::Comma)?;
+            let third = self.next_value()?;
+            let alpha = match self.next_token()? {
+                Arg {
+                    token: Token::Comma,
+                    ..
+                } => {
+                    let alpha = self.next_value()?;
+                    self.expect(Token::CloseParen)?;
+                    Some(alpha)
+                }
+                Arg {
+                    token: Token::CloseParen,
+                    ..
+                } => None,
+                arg => return Err(arg.error(ErrorKind::ExpectedCloseParen)),
+            };
+            return Ok(Args {
+                channels: [first, second, third],
+                alpha,
+                legacy: true,
+            });
+        }
+
+        let second = Self::value(separator)?;
+        let third = self.next_value()?;
+        let alpha = match self.next_token()? {
+            Arg {
+                token: Token::Slash,
+                ..
+            } => {
+                let alpha = self.next_value()?;
+                self.expect(Token::CloseParen)?;
+                Some(alpha)
+            }
+            Arg {
+                token: Token::CloseParen,
+                ..
+            } => None,
+            arg => return Err(arg.error(ErrorKind::ExpectedCloseParen)),
+        };
+        Ok(Args {
+            channels: [first, second, third],
+            alpha,
+            legacy: false,
+        })
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        let arg = self.next_token()?;
+        if arg.token == expected {
+            Ok(())
+        } else if expected == Token::CloseParen {
+            Err(arg.error(ErrorKind::ExpectedCloseParen))
+        } else {
+            Err(arg.error(ErrorKind::ExpectedValue))
+        }
+    }
+
+    fn next_value(&mut self) -> Result<Arg<'a>> {
+        Self::value(self.next_token()?)
+    }
+
+    const fn value(arg: Arg<'a>) -> Result<Arg<'a>> {
+        match arg.token {
+            Token::Comma | Token::Slash | Token::CloseParen => {
+                Err(arg.error(ErrorKind::ExpectedValue))
+            }
+            _ => Ok(arg),
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Arg<'a>> {
+        self.skip_whitespace();
+        let offset = self.pos;
+        let arg = |token| Ok(Arg { offset, token });
+
+        let Some(ch) = self.rest().chars().next() else {
+            return Err(self.error(ErrorKind::ExpectedCloseParen));
+        };
+        match ch {
+            ',' | '/' | ')' => {
+                self.pos += 1;
+                arg(match ch {
+                    ',' => Token::Comma,
+                    '/' => Token::Slash,
+                    _ => Token::CloseParen,
+                })
+            }
+            '0'..='9' | '.' | '+' | '-' if self.starts_with_number() => {
+                let num = self.number()?;
+                if self.rest().starts_with('%') {
+                    self.pos += 1;
+                    arg(Token::Percentage(num))
+                } else {
+                    match self.ident() {
+                        "" => arg(Token::Number(num)),
+                        unit => arg(Token::Dimension(num, unit)),
+                    }
+                }
+            }
+            ch if is_ident_char(ch) => arg(Token::Ident(self.ident())),
+            ch => Err(self.error(ErrorKind::UnexpectedChar(ch))),
+        }
+    }
+
+    fn starts_with_number(&self) -> bool {
+        let rest = self.rest().trim_start_matches(['+', '-']);
+        let rest = rest.strip_prefix('.').unwrap_or(rest);
+        rest.starts_with(|ch: char| ch.is_ascii_digit())
+    }
+
+    fn number(&mut self) -> Result<f32> {
+        let rest = self.rest().as_bytes();
+        let mut len = usize::from(matches!(rest.first(), Some(b'+' | b'-')));
+        let digits = |from: usize| {
+            from + rest[from..]
+                .iter()
+                .take_while(|ch| ch.is_ascii_digit())
+                .count()
+        };
+
+        len = digits(len);
+        if rest.get(len) == Some(&b'.') {
+            len = digits(len + 1);
+        }
+        // Exponent is part of the number only if digits follow it, otherwise
+        // it is the unit, e.g. "1em".
+        if let Some(b'e' | b'E') = rest.get(len) {
+            let sign = usize::from(matches!(rest.get(len + 1), Some(b'+' | b'-')));
+            let end = digits(len + 1 + sign);
+            if end > len + 1 + sign {
+                len = end;
+            }
+        }
+
+        let num = self.rest()[..len]
+            .parse::<f32>()
+            .map_err(|_| self.error(ErrorKind::ExpectedValue))?;
+        self.pos += len;
+        Ok(num)
+    }
+
+    fn ident(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|ch: char| !is_ident_char(ch))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
+
+const fn is_ident_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '-' || ch == '_'
+}
+
+fn hsl_to_srgb([h, s, l]: [f32; 3]) -> [f32; 3] {
+    let hsl = unsafe {
+        Hsl {
+            h: NotNan::new_unchecked(h),
+            s: NotNan::new_unchecked(s.clamp(0.0, 1.0)),
+            l: NotNan::new_unchecked(l.clamp(0.0, 1.0)),
+        }
+    };
+    hsl.to_srgb()
+}
+
+fn hwb_to_srgb([h, w, b]: [f32; 3]) -> [f32; 3] {
+    let hwb = unsafe {
+        Hwb {
+            h: NotNan::new_unchecked(h),
+            w: NotNan::new_unchecked(w.clamp(0.0, 1.0)),
+            b: NotNan::new_unchecked(b.clamp(0.0, 1.0)),
+        }
+    };
+    hwb.to_srgb()
+}
+
+/**
+    CSS uses D50 reference white for Lab, so it is adapted to D65 with
+    Bradford transform.
+
+    <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+*/
+fn lab_d50_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let lab = unsafe {
+        Cielab {
+            l: NotNan::new_unchecked(l.clamp(0.0, 100.0)),
+            a: NotNan::new_unchecked(a),
+            b: NotNan::new_unchecked(b),
+        }
+    };
+    lab.to_xyz(Illuminant::D50)
+        .adapt(Illuminant::D50, Illuminant::D65, Adaptation::Bradford)
+        .to_linear_srgb()
+}
+
+fn oklab_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let lab = unsafe {
+        Oklab {
+            l: NotNan::new_unchecked(l.clamp(0.0, 1.0)),
+            a: NotNan::new_unchecked(a),
+            b: NotNan::new_unchecked(b),
+        }
+    };
+    lab.to_linear_srgb()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_hex_ok() {
+        let tests = [
+            ("#0af", rgb(0, 170, 255)),
+            ("#0af8", rgb(0, 170, 255)),
+            ("#00AAff", rgb(0, 170, 255)),
+            ("#00aaff80", rgb(0, 170, 255)),
+            ("00aaff", rgb(0, 170, 255)),
+            ("  #00aaff\n", rgb(0, 170, 255)),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(parse(input), Ok(expected), "input: {input}");
+        }
+    }
+
+    #[test]
+    fn parse_named_ok() {
+        let tests = [
+            ("coral", rgb(255, 127, 80)),
+            ("Aqua", rgb(0, 255, 255)),
+            ("GRAY", rgb(128, 128, 128)),
+            ("rebeccapurple", rgb(102, 51, 153)),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(parse(input), Ok(expected), "input: {input}");
+        }
+    }
+
+    #[test]
+    fn parse_functions_ok() {
+        let tests = [
+            ("rgb(255, 127, 80)", rgb(255, 127, 80)),
+            ("rgba(255, 127, 80, 0.5)", rgb(255, 127, 80)),
+            ("rgb(255 127 80)", rgb(255, 127, 80)),
+            ("RGB(255 127 80 / 50%)", rgb(255, 127, 80)),
+            ("rgb(100% 50% 0%)", rgb(255, 128, 0)),
+            ("rgb(300 -10 none)", rgb(255, 0, 0)),
+            ("rgb(2.55e2 0 0)", rgb(255, 0, 0)),
+            ("hsl(120, 100%, 25%)", rgb(0, 128, 0)),
+            ("hsla(120deg 100% 25% / .5)", rgb(0, 128, 0)),
+            ("hsl(0.5turn 100 50)", rgb(0, 255, 255)),
+            ("hsl(-120 100% 50%)", rgb(0, 0, 255)),
+            ("hsl(3.14159rad 100% 50%)", rgb(0, 255, 255)),
+            ("hsl(200grad 100% 50%)", rgb(0, 255, 255)),
+            ("hwb(0 0% 0%)", rgb(255, 0, 0)),
+            ("hwb(120 20% 20%)", rgb(51, 204, 51)),
+            ("hwb(0 60% 60%)", rgb(128, 128, 128)),
+            ("lab(100 0 0)", rgb(255, 255, 255)),
+            ("lab(0% 0 0)", rgb(0, 0, 0)),
+            // Examples from the spec.
+            ("lab(29.2345% 39.3825 20.0664)", rgb(125, 35, 41)),
+            ("lch(29.2345% 44.2 27)", rgb(125, 35, 41)),
+            ("lch(52.2345% 72.2 56.2)", rgb(198, 93, 6)),
+            ("oklab(1 0 0)", rgb(255, 255, 255)),
+            ("oklab(62.8% 0.225 0.126)", rgb(255, 0, 0)),
+            ("oklch(62.8% 64.425% 29.23)", rgb(255, 0, 0)),
+            ("oklch(45.2% 0.3132 264.05deg / 1)", rgb(0, 0, 255)),
+            ("color(srgb 1 0.5 0)", rgb(255, 128, 0)),
+            ("color(srgb 100% 50% 0% / 0.2)", rgb(255, 128, 0)),
+            ("color(srgb-linear 1 0.2158 0)", rgb(255, 128, 0)),
+            ("color(display-p3 0.9175 0.2002 0.1386)", rgb(255, 0, 0)),
+            // Out of sRGB gamut, chroma is reduced keeping the hue.
+            ("color(display-p3 1 0 0)", rgb(255, 11, 12)),
+            ("color(Rec2020 0.7919 0.2309 0.0738)", rgb(255, 0, 0)),
+            ("color(a98-rgb 0.565 1 0.2345)", rgb(0, 255, 0)),
+            ("color(prophoto-rgb 0.3362 0.1376 0.9229)", rgb(0, 0, 255)),
+            ("color(prophoto-rgb 100% 100% 100%)", rgb(255, 255, 255)),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(parse(input), Ok(expected), "input: {input}");
+        }
+    }
+
+    #[test]
+    fn parse_out_of_gamut() {
+        let tests = [
+            ("rgb(300 0 0)", rgb(255, 0, 0), rgb(255, 0, 0), false),
+            ("hsl(0 150% 50%)", rgb(255, 0, 0), rgb(255, 0, 0), false),
+            (
+                "lab(100 0 0)",
+                rgb(255, 255, 255),
+                rgb(255, 255, 255),
+                false,
+            ),
+            (
+                "color(srgb 1.2 0.5 0)",
+                rgb(255, 128, 0),
+                rgb(255, 159, 110),
+                true,
+            ),
+            (
+                "color(display-p3 0 1 0)",
+                rgb(0, 255, 0),
+                rgb(0, 251, 41),
+                true,
+            ),
+            ("lab(50 -120 30)", rgb(0, 154, 60), rgb(0, 133, 90), true),
+            // Lightness is clamped before the conversion.
+            (
+                "lab(150 0 0)",
+                rgb(255, 255, 255),
+                rgb(255, 255, 255),
+                false,
+            ),
+            (
+                "oklab(1.5 0 0)",
+                rgb(255, 255, 255),
+                rgb(255, 255, 255),
+                false,
+            ),
+            ("oklch(70% 0.3 150)", rgb(0, 203, 0), rgb(0, 194, 72), true),
+        ];
+        for (input, clipped, mapped, clamped) in tests {
+            let parse = |mapping| parse_mapped(input, mapping).map(|res| (res.color, res.clamped));
+            assert_eq!(
+                parse(GamutMapping::Clip),
+                Ok((clipped, clamped)),
+                "input: {input}"
+            );
+            assert_eq!(
+                parse(GamutMapping::Css),
+                Ok((mapped, clamped)),
+                "input: {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_keeps_precision() {
+        let parse = |input| parse_mapped(input, GamutMapping::Css).unwrap();
+
+        let lab = parse("lab(50 20 30)").xyz().adapt(
+            Illuminant::D65,
+            Illuminant::D50,
+            Adaptation::Bradford,
+        );
+        let lab = Cielab::from_xyz(lab, Illuminant::D50);
+        assert!((lab.l() - 50.0).abs() < 0.01, "{lab}");
+        assert!((lab.a() - 20.0).abs() < 0.01, "{lab}");
+        assert!((lab.b() - 30.0).abs() < 0.01, "{lab}");
+
+        let hsl = Hsl::from_srgb(parse("hsl(200 30% 40%)").srgb);
+        assert!((hsl.h() - 200.0).abs() < 0.01, "{hsl}");
+        assert!((hsl.s() - 0.3).abs() < 0.0001, "{hsl}");
+        assert!((hsl.l() - 0.4).abs() < 0.0001, "{hsl}");
+    }
+
+    #[test]
+    fn parse_error() {
+        use ErrorKind::*;
+
+        let tests = [
+            ("", 0, Empty),
+            ("  ", 2, Empty),
+            ("#00aaf", 1, HexWrongLen(5)),
+            ("#00aafg", 6, NotAsciiHexDigit(b'g')),
+            ("0aaff", 0, HexWrongLen(5)),
+            ("notacolor", 0, UnknownName),
+            (" notacolor", 1, UnknownName),
+            ("rgc(0 0 0)", 0, UnknownFunction),
+            ("rgb(0 0)", 7, ExpectedValue),
+            ("rgb(0 0 0", 9, ExpectedCloseParen),
+            ("rgb(0 0 0 0)", 10, ExpectedCloseParen),
+            ("rgb(0, 0 0)", 9, ExpectedValue),
+            ("rgb(0 0 0) x", 11, TrailingCharacters),
+            ("rgb(0 0 #)", 8, UnexpectedChar('#')),
+            ("rgb(0 0 0deg)", 8, InvalidValue),
+            ("rgb(0 0 0 / red)", 12, InvalidValue),
+            ("hsl(10% 0% 0%)", 4, InvalidValue),
+            ("hsl(10px 0% 0%)", 4, InvalidValue),
+            ("hwb(0, 0%, 0%)", 5, ExpectedValue),
+            ("color(display-p4 0 0 0)", 6, UnknownColorSpace),
+            ("color(0 0 0)", 6, UnknownColorSpace),
+            // Values that overflow.
+            ("rgb(1e39 0 0)", 4, InvalidValue),
+            ("lab(1e39 0 0)", 4, InvalidValue),
+            ("oklch(0.5 1e38 30)", 10, InvalidValue),
+            ("color(display-p3 1e30 0 0)", 17, InvalidValue),
+            ("color(srgb 0 0 1e30)", 15, InvalidValue),
+            // Legacy syntax.
+            ("rgb(10%, 20, 30)", 9, InvalidValue),
+            ("rgb(10, 20, 30%)", 12, InvalidValue),
+            ("rgb(none, 0, 0)", 4, InvalidValue),
+            ("rgb(0, 0, 0, none)", 13, InvalidValue),
+            ("hsl(120, 100, 50%)", 9, InvalidValue),
+        ];
+        for (input, offset, kind) in tests {
+            assert_eq!(parse(input), Err(Error { offset, kind }), "input: {input}");
+        }
+    }
+}
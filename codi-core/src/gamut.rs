@@ -0,0 +1,288 @@
+use libm::{hypotf, sqrtf};
+use ordered_float::NotNan;
+
+use crate::color_space::{Oklab, Rgb, Xyz};
+use crate::rgb_space::TransferFunction;
+
+/// Just noticeable difference in Oklab used by CSS gamut mapping.
+const JND: f32 = 0.02;
+/// Precision of the chroma binary search.
+const CHROMA_EPSILON: f32 = 0.0001;
+/// Encoded channels that round to the same 8-bit value as the gamut boundary
+/// are considered in gamut.
+const CHANNEL_EPSILON: f32 = 0.5 / 255.0;
+
+/**
+    Strategy to bring colors outside of the sRGB gamut inside of it.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GamutMapping {
+    /// Clamps each channel independently. It is fast, but can noticeably
+    /// shift hue and lightness of the color.
+    Clip,
+    /// Reduces chroma in Oklch keeping lightness and hue until the clipped
+    /// color is indistinguishable from the reduced one.
+    ///
+    /// Spec: <https://www.w3.org/TR/css-color-4/#binsearch>
+    #[default]
+    Css,
+}
+
+/**
+    Result of converting color into sRGB.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mapped {
+    /// Color rounded to 8-bit channels.
+    pub color: Rgb,
+    /// Gamma-corrected channels of the same color in range [0, 1] without
+    /// rounding.
+    pub srgb: [f32; 3],
+    /// Whether the color was outside of the gamut and had to be changed.
+    pub clamped: bool,
+}
+
+impl Mapped {
+    /**
+        Color from linear sRGB channels inside of the gamut.
+    */
+    fn from_linear(linear: [f32; 3], clamped: bool) -> Self {
+        Self {
+            color: Rgb::from_linear(linear),
+            srgb: linear.map(|col| TransferFunction::Srgb.encode(col.clamp(0.0, 1.0))),
+            clamped,
+        }
+    }
+
+    /**
+        Color from gamma-corrected sRGB channels, which are clamped into range
+        [0, 1].
+    */
+    pub(crate) fn from_srgb(srgb: [f32; 3]) -> Self {
+        let srgb = srgb.map(|col| col.clamp(0.0, 1.0));
+        Self {
+            color: Rgb::from_unit(srgb),
+            srgb,
+            clamped: false,
+        }
+    }
+
+    /**
+        Converts the color without rounding into XYZ.
+    */
+    pub fn xyz(&self) -> Xyz {
+        Xyz::from_linear_srgb(self.srgb.map(|col| TransferFunction::Srgb.decode(col)))
+    }
+}
+
+impl From<Rgb> for Mapped {
+    fn from(color: Rgb) -> Self {
+        Self {
+            color,
+            srgb: color.to_unit(),
+            clamped: false,
+        }
+    }
+}
+
+/**
+    Checks whether color can be represented in 8-bit sRGB without clamping.
+
+    # Example
+
+    ```
+    use codi_core::color_space::{Cielab, Oklch};
+    use codi_core::gamut::in_gamut;
+
+    assert!(in_gamut(Cielab::new(53.24, 80.09, 67.2).unwrap()));
+    assert!(!in_gamut(Oklch::new(0.7, 0.3, 150.0).unwrap()));
+    ```
+*/
+pub fn in_gamut(color: impl Into<Oklab>) -> bool {
+    in_gamut_linear(color.into().to_linear_srgb())
+}
+
+impl GamutMapping {
+    pub const ALL: [Self; 2] = [Self::Clip, Self::Css];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Clip => "clip",
+            Self::Css => "css",
+        }
+    }
+
+    /**
+        Converts color into 8-bit sRGB, colors outside of the gamut are
+        mapped with this strategy.
+
+        # Example
+
+        ```
+        use codi_core::color_space::{Oklch, rgb};
+        use codi_core::gamut::GamutMapping;
+
+        let color = Oklch::new(0.7, 0.3, 150.0).unwrap();
+        let clipped = GamutMapping::Clip.map(color);
+        let mapped = GamutMapping::Css.map(color);
+        assert!(clipped.clamped && mapped.clamped);
+        assert_eq!(clipped.color, rgb(0, 203, 0));
+        assert_eq!(mapped.color, rgb(0, 194, 72));
+        ```
+    */
+    pub fn map(self, color: impl Into<Oklab>) -> Mapped {
+        self.map_linear(color.into().to_linear_srgb())
+    }
+
+    /**
+        Maps linear sRGB channels into the gamut.
+    */
+    pub(crate) fn map_linear(self, linear: [f32; 3]) -> Mapped {
+        if in_gamut_linear(linear) {
+            return Mapped::from_linear(clip(linear), false);
+        }
+
+        let linear = match self {
+            Self::Clip => clip(linear),
+            Self::Css => reduce_chroma(linear),
+        };
+        Mapped::from_linear(linear, true)
+    }
+}
+
+impl core::fmt::Display for GamutMapping {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+fn in_gamut_linear(linear: [f32; 3]) -> bool {
+    linear.into_iter().all(|col| {
+        let col = TransferFunction::Srgb.encode(col);
+        (-CHANNEL_EPSILON..=1.0 + CHANNEL_EPSILON).contains(&col)
+    })
+}
+
+fn clip(linear: [f32; 3]) -> [f32; 3] {
+    linear.map(|col| col.clamp(0.0, 1.0))
+}
+
+/**
+    Binary search of the chroma, where the clipped color is closer than
+    [`JND`] to the color with reduced chroma.
+*/
+#[allow(clippy::while_float)]
+fn reduce_chroma(linear: [f32; 3]) -> [f32; 3] {
+    let delta_eok = |lab1: Oklab, lab2: Oklab| {
+        let (dl, da, db) = (
+            lab1.l() - lab2.l(),
+            lab1.a() - lab2.a(),
+            lab1.b() - lab2.b(),
+        );
+        sqrtf(dl * dl + da * da + db * db)
+    };
+
+    let origin = Oklab::from_linear_srgb(linear);
+    if *origin.l >= 1.0 {
+        return [1.0; 3];
+    }
+    if *origin.l <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let mut clipped = clip(linear);
+    if delta_eok(Oklab::from_linear_srgb(clipped), origin) < JND {
+        return clipped;
+    }
+
+    let origin_chroma = hypotf(*origin.a, *origin.b);
+    let with_chroma = |chroma: f32| {
+        let scale = chroma / origin_chroma;
+        unsafe {
+            Oklab {
+                l: origin.l,
+                a: NotNan::new_unchecked(*origin.a * scale),
+                b: NotNan::new_unchecked(*origin.b * scale),
+            }
+        }
+    };
+
+    let (mut min, mut max, mut min_in_gamut) = (0.0, origin_chroma, true);
+    while max - min > CHROMA_EPSILON {
+        let chroma = f32::midpoint(min, max);
+        let current = with_chroma(chroma);
+        let current_linear = current.to_linear_srgb();
+        if min_in_gamut && in_gamut_linear(current_linear) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(current_linear);
+        let delta = delta_eok(Oklab::from_linear_srgb(clipped), current);
+        if delta < JND {
+            if JND - delta < CHROMA_EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::{Cielab, Oklch, rgb};
+    use crate::rgb_space::RgbSpace;
+
+    #[test]
+    fn in_gamut_colors_are_unchanged() {
+        for color in [
+            rgb(0, 0, 0),
+            rgb(255, 255, 255),
+            rgb(255, 0, 0),
+            rgb(18, 52, 86),
+        ] {
+            assert!(in_gamut(color));
+            for mapping in GamutMapping::ALL {
+                let mapped = mapping.map(Xyz::from(color));
+                assert_eq!(mapped.color, color, "{mapping}");
+                assert!(!mapped.clamped, "{mapping}");
+            }
+        }
+    }
+
+    #[test]
+    fn lightness_out_of_range() {
+        let white = Oklch::new(1.2, 0.2, 100.0).unwrap();
+        let black = Oklch::new(-0.1, 0.2, 100.0).unwrap();
+        assert_eq!(GamutMapping::Css.map(white).color, rgb(255, 255, 255));
+        assert_eq!(GamutMapping::Css.map(black).color, rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn css_keeps_hue_and_lightness() {
+        let p3_green = RgbSpace::DISPLAY_P3.to_xyz([0.0, 1.0, 0.0]).unwrap();
+        let lab = Cielab::new(50.0, -120.0, 30.0).unwrap();
+        for origin in [
+            Oklch::from(Oklab::from(p3_green)),
+            Oklch::from(Oklab::from(lab)),
+        ] {
+            assert!(!in_gamut(Oklab::from(origin)));
+
+            let mapped = GamutMapping::Css.map(origin);
+            assert!(mapped.clamped);
+            let mapped = Oklch::from(mapped.color);
+            assert!((mapped.l() - origin.l()).abs() < 0.02, "{mapped} {origin}");
+            assert!((mapped.h() - origin.h()).abs() < 5.0, "{mapped} {origin}");
+            assert!(mapped.c() < origin.c());
+
+            let clipped = Oklch::from(GamutMapping::Clip.map(origin).color);
+            assert!((clipped.l() - origin.l()).abs() > (mapped.l() - origin.l()).abs());
+            assert!((clipped.h() - origin.h()).abs() > (mapped.h() - origin.h()).abs());
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::color_dist::ColorDistance;
+use crate::color_dist::{ColorDistance, Match};
 use crate::color_space::{Rgb, rgb};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -49,11 +49,14 @@ const fn __split_colors_arr<const N: usize>() -> ([&'static str; N], [Rgb; N]) {
     Find closest to target color from list of named html colors.
 */
 #[allow(clippy::missing_panics_doc)]
-pub fn find_closest<T: ColorDistance + ?Sized>(alg: &T, target: Rgb) -> HtmlColor {
+pub fn find_closest<T: ColorDistance + ?Sized>(alg: &T, target: Rgb) -> Match<HtmlColor> {
     let idx = alg
         .find_closest(target, &ONLY_COLORS)
         .expect("SAFETY: we have assert for const array ONLY_COLORS length");
-    COLORS[idx]
+    Match {
+        color: COLORS[idx],
+        distance: alg.dist(target, COLORS[idx].color),
+    }
 }
 
 pub fn find_exact(target: Rgb) -> Option<&'static str> {
@@ -0,0 +1,269 @@
+use ordered_float::NotNan;
+
+use crate::color_space::Xyz;
+use crate::math_utils::{column, matrix_inverse, matrix_mul_f32};
+
+/**
+    Standard illuminants of the CIE 1931 2° observer used as reference white.
+
+    White points: <http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html>
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Illuminant {
+    /// Incandescent lamp.
+    A,
+    /// Horizon light, used by ICC profiles and printing.
+    D50,
+    /// Mid-morning daylight.
+    D55,
+    /// Noon daylight, used by sRGB.
+    D65,
+    /// North sky daylight.
+    D75,
+    /// Equal energy.
+    E,
+    /// Cool white fluorescent lamp.
+    F2,
+    /// Broad-band daylight fluorescent lamp.
+    F7,
+    /// Narrow tri-band fluorescent lamp.
+    F11,
+}
+
+impl Illuminant {
+    pub const ALL: [Self; 9] = [
+        Self::A,
+        Self::D50,
+        Self::D55,
+        Self::D65,
+        Self::D75,
+        Self::E,
+        Self::F2,
+        Self::F7,
+        Self::F11,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::D50 => "D50",
+            Self::D55 => "D55",
+            Self::D65 => "D65",
+            Self::D75 => "D75",
+            Self::E => "E",
+            Self::F2 => "F2",
+            Self::F7 => "F7",
+            Self::F11 => "F11",
+        }
+    }
+
+    /**
+        Find illuminant by its name, case-insensitive.
+
+        # Example
+
+        ```
+        use codi_core::illuminant::Illuminant;
+        assert_eq!(Illuminant::find("d50"), Some(Illuminant::D50));
+        assert_eq!(Illuminant::find("D60"), None);
+        ```
+    */
+    pub fn find(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|illuminant| illuminant.name().eq_ignore_ascii_case(name))
+    }
+
+    /**
+        Tristimulus values of the white point with `Y` equal to `1.0`.
+    */
+    pub const fn white_point(self) -> Xyz {
+        let [x, y, z] = self.tristimulus();
+        unsafe {
+            Xyz {
+                x: NotNan::new_unchecked(x),
+                y: NotNan::new_unchecked(y),
+                z: NotNan::new_unchecked(z),
+            }
+        }
+    }
+
+    /**
+        Same as [`Self::white_point`], but as an array for the constant
+        matrices.
+    */
+    pub(crate) const fn tristimulus(self) -> [f32; 3] {
+        match self {
+            Self::A => [1.098_50, 1.0, 0.355_85],
+            Self::D50 => [0.964_22, 1.0, 0.825_21],
+            Self::D55 => [0.956_82, 1.0, 0.921_49],
+            Self::D65 => [0.950_489, 1.0, 1.088_84],
+            Self::D75 => [0.949_72, 1.0, 1.226_38],
+            Self::E => [1.0, 1.0, 1.0],
+            Self::F2 => [0.991_87, 1.0, 0.673_95],
+            Self::F7 => [0.950_44, 1.0, 1.087_55],
+            Self::F11 => [1.009_66, 1.0, 0.643_70],
+        }
+    }
+}
+
+impl core::fmt::Display for Illuminant {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/**
+    Chromatic adaptation transform that converts colors between reference
+    whites, see [`Xyz::adapt`].
+
+    Info: <http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html>
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Adaptation {
+    #[default]
+    Bradford,
+    VonKries,
+    /// Transform from the CIECAM02 color appearance model.
+    Cat02,
+    /// Scaling of the XYZ values, the least accurate one.
+    XyzScaling,
+}
+
+impl Adaptation {
+    /**
+        Matrix to convert XYZ into cone response domain.
+    */
+    const fn cone_response(self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Bradford => [
+                [0.8951, 0.2664, -0.1614],
+                [-0.7502, 1.7135, 0.0367],
+                [0.0389, -0.0685, 1.0296],
+            ],
+            Self::VonKries => [
+                [0.400_24, 0.7076, -0.080_81],
+                [-0.2263, 1.165_32, 0.0457],
+                [0.0, 0.0, 0.918_22],
+            ],
+            Self::Cat02 => [
+                [0.7328, 0.4296, -0.1624],
+                [-0.7036, 1.6975, 0.0061],
+                [0.0030, 0.0136, 0.9834],
+            ],
+            Self::XyzScaling => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /**
+        Matrix that converts XYZ relative to `src` white into XYZ relative to
+        `dst` white.
+    */
+    pub const fn matrix(self, src: Illuminant, dst: Illuminant) -> [[f32; 3]; 3] {
+        let cone = self.cone_response();
+        let src_cone = matrix_mul_f32(&cone, &column(src.tristimulus()));
+        let dst_cone = matrix_mul_f32(&cone, &column(dst.tristimulus()));
+
+        let mut scale = [[0.0; 3]; 3];
+        let mut idx = 0;
+        while idx < 3 {
+            scale[idx][idx] = dst_cone[idx][0] / src_cone[idx][0];
+            idx += 1;
+        }
+        matrix_mul_f32(
+            &self.inverse_cone_response(),
+            &matrix_mul_f32(&scale, &cone),
+        )
+    }
+
+    /**
+        Inverse of [`Self::cone_response`], computed once for each method.
+    */
+    const fn inverse_cone_response(self) -> [[f32; 3]; 3] {
+        const BRADFORD: [[f32; 3]; 3] = matrix_inverse(&Adaptation::Bradford.cone_response());
+        const VON_KRIES: [[f32; 3]; 3] = matrix_inverse(&Adaptation::VonKries.cone_response());
+        const CAT02: [[f32; 3]; 3] = matrix_inverse(&Adaptation::Cat02.cone_response());
+        const XYZ_SCALING: [[f32; 3]; 3] = matrix_inverse(&Adaptation::XyzScaling.cone_response());
+
+        match self {
+            Self::Bradford => BRADFORD,
+            Self::VonKries => VON_KRIES,
+            Self::Cat02 => CAT02,
+            Self::XyzScaling => XYZ_SCALING,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const METHODS: [Adaptation; 4] = [
+        Adaptation::Bradford,
+        Adaptation::VonKries,
+        Adaptation::Cat02,
+        Adaptation::XyzScaling,
+    ];
+
+    fn assert_xyz_close(actual: Xyz, expected: Xyz, tolerance: f32) {
+        assert!(
+            (actual.x() - expected.x()).abs() < tolerance
+                && (actual.y() - expected.y()).abs() < tolerance
+                && (actual.z() - expected.z()).abs() < tolerance,
+            "got: {actual}, expected: {expected}"
+        );
+    }
+
+    #[test]
+    fn adapt_white_point() {
+        for method in METHODS {
+            for src in Illuminant::ALL {
+                for dst in Illuminant::ALL {
+                    let white = src.white_point().adapt(src, dst, method);
+                    assert_xyz_close(white, dst.white_point(), 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adapt_round_trip() {
+        let color = Xyz::new(0.3, 0.2, 0.7).unwrap();
+        for method in METHODS {
+            let adapted = color.adapt(Illuminant::D65, Illuminant::A, method);
+            let back = adapted.adapt(Illuminant::A, Illuminant::D65, method);
+            assert_xyz_close(back, color, 1e-5);
+            assert_xyz_close(
+                color.adapt(Illuminant::D65, Illuminant::D65, method),
+                color,
+                1e-6,
+            );
+        }
+    }
+
+    #[test]
+    fn bradford_d65_to_d50_matrix() {
+        // http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html
+        let expected = [
+            [1.047_811_2, 0.022_886_6, -0.050_127],
+            [0.029_542_4, 0.990_484_4, -0.017_049_1],
+            [-0.009_234_5, 0.015_043_6, 0.752_131_6],
+        ];
+        let actual = Adaptation::Bradford.matrix(Illuminant::D65, Illuminant::D50);
+        for (actual, expected) in actual.iter().flatten().zip(expected.iter().flatten()) {
+            assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn find_by_name() {
+        for illuminant in Illuminant::ALL {
+            assert_eq!(Illuminant::find(illuminant.name()), Some(illuminant));
+            assert_eq!(
+                Illuminant::find(&illuminant.to_string().to_lowercase()),
+                Some(illuminant)
+            );
+        }
+        assert_eq!(Illuminant::find(""), None);
+    }
+}
@@ -1,6 +1,16 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod cam16;
 pub mod color_dist;
 pub mod color_space;
+pub mod css;
+pub mod gamut;
 pub mod html_color;
+pub mod illuminant;
 pub(crate) mod math_utils;
+#[cfg(feature = "alloc")]
+pub mod palette;
+pub mod rgb_space;
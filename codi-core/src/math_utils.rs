@@ -22,6 +22,89 @@ where
     res
 }
 
+/**
+    Same as [`matrix_mul`] for `f32`, it can be used in constants.
+*/
+pub const fn matrix_mul_f32<const I: usize, const J: usize, const K: usize>(
+    m1: &[[f32; K]; I],
+    m2: &[[f32; J]; K],
+) -> [[f32; J]; I] {
+    let mut res = [[0.0; J]; I];
+
+    let mut i = 0;
+    while i < I {
+        let mut j = 0;
+        while j < J {
+            let mut k = 0;
+            while k < K {
+                res[i][j] += m1[i][k] * m2[k][j];
+                k += 1;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+
+    res
+}
+
+/**
+    Column vector to multiply the 3x3 matrix by.
+*/
+pub const fn column([x, y, z]: [f32; 3]) -> [[f32; 1]; 3] {
+    [[x], [y], [z]]
+}
+
+/**
+    Inverse of the 3x3 matrix, it must be invertible.
+*/
+pub const fn matrix_inverse(m: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let det =
+        m[0][0] * cofactor(m, 0, 0) + m[0][1] * cofactor(m, 0, 1) + m[0][2] * cofactor(m, 0, 2);
+
+    let mut res = [[0.0; 3]; 3];
+    let mut row = 0;
+    while row < 3 {
+        let mut col = 0;
+        while col < 3 {
+            res[row][col] = cofactor(m, col, row) / det;
+            col += 1;
+        }
+        row += 1;
+    }
+    res
+}
+
+/**
+    Cofactor of the 3x3 matrix element. Indices are cyclic, so minors already
+    have the cofactor sign.
+*/
+const fn cofactor(m: &[[f32; 3]; 3], row: usize, col: usize) -> f32 {
+    let (r1, r2) = ((row + 1) % 3, (row + 2) % 3);
+    let (c1, c2) = ((col + 1) % 3, (col + 2) % 3);
+    m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1]
+}
+
+/**
+    Hue angle of the `(a, b)` vector in degrees in range [0, 360).
+*/
+pub fn hue_angle(a: f32, b: f32) -> f32 {
+    if a == 0.0 && b == 0.0 {
+        return 0.0;
+    }
+    let hue = libm::atan2f(b, a).to_degrees();
+    if hue < 0.0 { hue + 360.0 } else { hue }
+}
+
+/**
+    Least non-negative remainder of `lhs / rhs`, same as [`f32::rem_euclid`],
+    which is not available in `no_std`.
+*/
+pub fn rem_euclid(lhs: f32, rhs: f32) -> f32 {
+    let rem = libm::fmodf(lhs, rhs);
+    if rem < 0.0 { rem + rhs.abs() } else { rem }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -41,4 +124,34 @@ mod test {
         let answer = [[30, 36, 42], [66, 81, 96], [102, 126, 150]];
         assert_eq!(matrix_mul(&matrix, &matrix), answer);
     }
+
+    #[test]
+    fn matrix_mul_f32_same_as_generic() {
+        let matrix = [[1.5, -2.0, 3.25], [4.0, 0.5, -6.0], [7.0, 8.0, 0.125]];
+        let vector = column([0.2, -0.4, 0.8]);
+        assert_eq!(
+            matrix_mul_f32(&matrix, &matrix),
+            matrix_mul(&matrix, &matrix)
+        );
+        assert_eq!(
+            matrix_mul_f32(&matrix, &vector),
+            matrix_mul(&matrix, &vector)
+        );
+    }
+
+    #[test]
+    fn matrix_inverse_identity() {
+        let matrix = [
+            [0.8951, 0.2664, -0.1614],
+            [-0.7502, 1.7135, 0.0367],
+            [0.0389, -0.0685, 1.0296],
+        ];
+        let identity = matrix_mul(&matrix, &matrix_inverse(&matrix));
+        for (row, values) in identity.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-6, "{identity:?}");
+            }
+        }
+    }
 }
@@ -0,0 +1,508 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ordered_float::NotNan;
+
+use crate::color_dist::{ColorDistance, Match};
+use crate::color_space::Rgb;
+use crate::html_color::{COLORS, HtmlColor};
+
+mod aco;
+mod ase;
+mod gpl;
+mod model;
+mod reader;
+mod x11;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Line has a name but no color.
+    MissingColor { line: usize },
+    /// Color is not in the format expected by the palette format.
+    InvalidColor { line: usize },
+    /// Palette format cannot be detected from the content.
+    UnknownFormat,
+    /// Text palette is not valid UTF-8.
+    NotUtf8,
+    /// File header doesn't match the palette format.
+    InvalidHeader,
+    /// Binary palette ends in the middle of the entry.
+    UnexpectedEof { offset: usize },
+    /// Binary palette has the color in a model we cannot convert to RGB.
+    UnsupportedColorModel { offset: usize },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::result::Result<(), core::fmt::Error> {
+        match self {
+            Self::MissingColor { line } => write!(f, "line {line}: color is missing"),
+            Self::InvalidColor { line } => write!(
+                f,
+                "line {line}: color must be in format \"rgb(r, g, b)\" or \"r g b\" with values from 0 to 255"
+            ),
+            Self::UnknownFormat => write!(f, "unknown palette format"),
+            Self::NotUtf8 => write!(f, "text palette must be valid UTF-8"),
+            Self::InvalidHeader => write!(f, "invalid palette header"),
+            Self::UnexpectedEof { offset } => {
+                write!(f, "byte {offset}: unexpected end of palette")
+            }
+            Self::UnsupportedColorModel { offset } => {
+                write!(f, "byte {offset}: unsupported color model")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/**
+    Supported palette file formats.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Format of "contrib/html-color-names.txt": "aliceblue rgb(240, 248, 255)".
+    Codi,
+    /// GIMP palette (.gpl).
+    Gpl,
+    /// Adobe Photoshop color swatches (.aco).
+    Aco,
+    /// Adobe swatch exchange (.ase).
+    Ase,
+    /// X11 rgb.txt: "240 248 255 alice blue".
+    X11,
+}
+
+impl Format {
+    /**
+        Detects palette format from the file content.
+    */
+    pub fn detect(content: &[u8]) -> Option<Self> {
+        if content.starts_with(ase::MAGIC) {
+            return Some(Self::Ase);
+        }
+        if let [0, 1 | 2, ..] = content {
+            return Some(Self::Aco);
+        }
+
+        let text = core::str::from_utf8(content).ok()?;
+        if text.starts_with(gpl::MAGIC) {
+            return Some(Self::Gpl);
+        }
+        // Both formats allow comments, so look at the first line with color.
+        let first = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !(line.is_empty() || line.starts_with(['#', '!'])))?;
+        if first.starts_with(|ch: char| ch.is_ascii_digit()) {
+            Some(Self::X11)
+        } else {
+            Some(Self::Codi)
+        }
+    }
+
+    /**
+        Guesses palette format from the file extension, e.g. "gpl".
+    */
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "gpl" => Some(Self::Gpl),
+            "aco" => Some(Self::Aco),
+            "ase" => Some(Self::Ase),
+            _ => None,
+        }
+    }
+}
+
+/**
+    Borrowed entry of the [`Palette`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteColor<'a> {
+    pub name: &'a str,
+    pub color: Rgb,
+}
+
+/**
+    List of named colors that can be built at runtime.
+
+    # Example
+
+    ```
+    use codi_core::color_dist::CIE94;
+    use codi_core::color_space::rgb;
+    use codi_core::palette::Palette;
+
+    let palette = Palette::from_iter([("brand-red", rgb(220, 20, 60)), ("brand-blue", rgb(0, 0, 139))]);
+    assert_eq!(palette.find_closest(&CIE94::default(), rgb(200, 0, 0)).unwrap().color.name, "brand-red");
+    ```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Palette {
+    // Names and colors are stored separately to pass colors as a slice to
+    // [`ColorDistance::find_closest`].
+    names: Vec<String>,
+    colors: Vec<Rgb>,
+}
+
+impl Palette {
+    pub const fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            colors: Vec::new(),
+        }
+    }
+
+    /**
+        Palette with all named html colors.
+    */
+    pub fn html() -> Self {
+        COLORS
+            .iter()
+            .map(|HtmlColor { name, color }| (*name, *color))
+            .collect()
+    }
+
+    /**
+        Parses palette in any of the supported [`Format`]s.
+
+        # Errors
+
+        Returns [`Error::UnknownFormat`] if format cannot be detected or
+        the error of the detected format parser.
+    */
+    pub fn parse(content: &[u8]) -> Result<Self> {
+        Self::parse_format(
+            content,
+            Format::detect(content).ok_or(Error::UnknownFormat)?,
+        )
+    }
+
+    /**
+        Parses palette in the given format.
+
+        # Errors
+
+        Returns [`Err`] if content doesn't match the format.
+    */
+    pub fn parse_format(content: &[u8], format: Format) -> Result<Self> {
+        let text = || core::str::from_utf8(content).map_err(|_| Error::NotUtf8);
+        match format {
+            Format::Codi => text()?.parse(),
+            Format::Gpl => gpl::parse(text()?),
+            Format::X11 => x11::parse(text()?),
+            Format::Aco => aco::parse(content),
+            Format::Ase => ase::parse(content),
+        }
+    }
+
+    pub fn push<S: Into<String>>(&mut self, name: S, color: Rgb) {
+        self.names.push(name.into());
+        self.colors.push(color);
+    }
+
+    pub const fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<PaletteColor<'_>> {
+        Some(PaletteColor {
+            name: self.names.get(idx)?,
+            color: *self.colors.get(idx)?,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = PaletteColor<'_>> {
+        self.names
+            .iter()
+            .zip(&self.colors)
+            .map(|(name, color)| PaletteColor {
+                name,
+                color: *color,
+            })
+    }
+
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors
+    }
+
+    /**
+        Find closest to target color from the palette.
+
+        # Returns
+        Closest color with its distance or [`Option::None`] if palette is empty.
+    */
+    pub fn find_closest<T: ColorDistance + ?Sized>(
+        &self,
+        alg: &T,
+        target: Rgb,
+    ) -> Option<Match<PaletteColor<'_>>> {
+        let color = self.get(alg.find_closest(target, &self.colors)?)?;
+        Some(Match {
+            color,
+            distance: alg.dist(target, color.color),
+        })
+    }
+
+    /**
+        Find `k` closest to target colors from the palette with their distances
+        sorted in ascending order, see [`ColorDistance::find_k_closest`].
+    */
+    pub fn find_k_closest<T: ColorDistance + ?Sized>(
+        &self,
+        alg: &T,
+        target: Rgb,
+        k: usize,
+    ) -> Vec<Match<PaletteColor<'_>>> {
+        self.to_matches(alg.find_k_closest(target, &self.colors, k))
+    }
+
+    /**
+        Find all colors from the palette within `max_distance` from the
+        target color sorted by distance in ascending order, see
+        [`ColorDistance::find_within`].
+    */
+    pub fn find_within<T: ColorDistance + ?Sized>(
+        &self,
+        alg: &T,
+        target: Rgb,
+        max_distance: f32,
+    ) -> Vec<Match<PaletteColor<'_>>> {
+        self.to_matches(alg.find_within(target, &self.colors, max_distance))
+    }
+
+    pub fn find_exact(&self, target: Rgb) -> Option<&str> {
+        self.iter()
+            .find(|PaletteColor { color, .. }| *color == target)
+            .map(|PaletteColor { name, .. }| name)
+    }
+
+    fn to_matches(&self, found: Vec<(usize, NotNan<f32>)>) -> Vec<Match<PaletteColor<'_>>> {
+        found
+            .into_iter()
+            .filter_map(|(idx, distance)| {
+                Some(Match {
+                    color: self.get(idx)?,
+                    distance,
+                })
+            })
+            .collect()
+    }
+}
+
+impl core::str::FromStr for Palette {
+    type Err = Error;
+
+    /**
+        Parses palette from the text with the same format as
+        "contrib/html-color-names.txt".
+
+        Line format: "aliceblue rgb(240, 248, 255)". Comments starting with '#'
+        and empty lines are allowed.
+
+        # Example
+
+        ```
+        use codi_core::color_space::rgb;
+        use codi_core::palette::Palette;
+
+        let palette: Palette = "# Brand colors\nprimary rgb(0, 95, 204)".parse().unwrap();
+        assert_eq!(palette.find_exact(rgb(0, 95, 204)), Some("primary"));
+        ```
+    */
+    fn from_str(content: &str) -> Result<Self> {
+        let mut palette = Self::new();
+
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((name, color)) = line.split_once(char::is_whitespace) else {
+                return Err(Error::MissingColor { line: idx + 1 });
+            };
+            let color = parse_rgb_fn(color.trim()).ok_or(Error::InvalidColor { line: idx + 1 })?;
+
+            palette.push(name, color);
+        }
+
+        Ok(palette)
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Rgb)> for Palette {
+    fn from_iter<I: IntoIterator<Item = (S, Rgb)>>(iter: I) -> Self {
+        let mut palette = Self::new();
+        palette.extend(iter);
+        palette
+    }
+}
+
+impl<S: Into<String>> Extend<(S, Rgb)> for Palette {
+    fn extend<I: IntoIterator<Item = (S, Rgb)>>(&mut self, iter: I) {
+        for (name, color) in iter {
+            self.push(name, color);
+        }
+    }
+}
+
+/**
+    Parses "rgb(r, g, b)" string.
+*/
+fn parse_rgb_fn(value: &str) -> Option<Rgb> {
+    let args = value.strip_prefix("rgb(")?.strip_suffix(')')?;
+    let mut channels = args.split(',').map(|ch| ch.trim().parse::<u8>());
+
+    let color = Rgb::new(
+        channels.next()?.ok()?,
+        channels.next()?.ok()?,
+        channels.next()?.ok()?,
+    );
+    channels.next().is_none().then_some(color)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_dist::ALGORITHMS;
+    use crate::color_space::rgb;
+    use crate::html_color;
+
+    #[test]
+    fn html_palette_same_as_html_colors() {
+        let palette = Palette::html();
+        assert_eq!(palette.len(), COLORS.len());
+
+        let tests = [rgb(0x81, 0x81, 0x8d), rgb(255, 85, 255), rgb(1, 2, 3)];
+        for target in tests {
+            for alg in ALGORITHMS {
+                let Match {
+                    color: HtmlColor { name, color },
+                    distance,
+                } = html_color::find_closest(alg, target);
+                assert_eq!(
+                    palette.find_closest(alg, target),
+                    Some(Match {
+                        color: PaletteColor { name, color },
+                        distance
+                    }),
+                    "input: {target}, algorithm: {alg}"
+                );
+            }
+            assert_eq!(palette.find_exact(target), html_color::find_exact(target));
+        }
+    }
+
+    #[test]
+    fn find_within() {
+        let palette = Palette::from_iter([
+            ("black", rgb(0, 0, 0)),
+            ("white", rgb(255, 255, 255)),
+            ("almost-white", rgb(254, 254, 254)),
+        ]);
+        for alg in ALGORITHMS {
+            let names: Vec<_> = palette
+                .find_within(alg, rgb(255, 255, 255), 5.0)
+                .into_iter()
+                .map(|found| found.color.name)
+                .collect();
+            assert_eq!(names, ["white", "almost-white"], "algorithm: {alg}");
+        }
+    }
+
+    #[test]
+    fn empty_palette() {
+        let palette = Palette::new();
+        assert!(palette.is_empty());
+        assert_eq!(palette.find_closest(ALGORITHMS[0], rgb(0, 0, 0)), None);
+        assert_eq!(palette.find_exact(rgb(0, 0, 0)), None);
+    }
+
+    #[test]
+    fn custom_palette() {
+        let mut palette = Palette::from_iter([("black", rgb(0, 0, 0))]);
+        palette.push(String::from("white"), rgb(255, 255, 255));
+
+        assert_eq!(palette.find_exact(rgb(255, 255, 255)), Some("white"));
+        for alg in ALGORITHMS {
+            assert_eq!(
+                palette
+                    .find_closest(alg, rgb(10, 10, 10))
+                    .map(|c| c.color.name),
+                Some("black")
+            );
+            assert_eq!(
+                palette
+                    .find_closest(alg, rgb(240, 240, 240))
+                    .map(|c| c.color.name),
+                Some("white")
+            );
+        }
+    }
+
+    #[test]
+    fn parse_palette_ok() {
+        let content = "\
+# Comment
+
+primary   rgb(0, 95, 204)
+  secondary rgb(255,255,255)  
+";
+        let palette: Palette = content.parse().unwrap();
+        assert_eq!(
+            palette,
+            Palette::from_iter([
+                ("primary", rgb(0, 95, 204)),
+                ("secondary", rgb(255, 255, 255))
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_palette_error() {
+        let tests = [
+            ("primary", Error::MissingColor { line: 1 }),
+            ("\nprimary rgb(0, 0)", Error::InvalidColor { line: 2 }),
+            ("primary rgb(0, 0, 256)", Error::InvalidColor { line: 1 }),
+            ("primary rgb(0, 0, 0, 0)", Error::InvalidColor { line: 1 }),
+            ("primary #000000", Error::InvalidColor { line: 1 }),
+        ];
+        for (content, expected) in tests {
+            assert_eq!(
+                content.parse::<Palette>(),
+                Err(expected),
+                "input: {content}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_html_color_names_file() {
+        let content = include_str!("../../contrib/html-color-names.txt");
+        assert_eq!(content.parse::<Palette>(), Ok(Palette::html()));
+    }
+
+    #[test]
+    fn detect_format() {
+        let tests = [
+            (&b"ASEF\0\x01\0\0"[..], Some(Format::Ase)),
+            (b"\0\x01\0\0", Some(Format::Aco)),
+            (b"\0\x02\0\0", Some(Format::Aco)),
+            (b"GIMP Palette\nName: Brand", Some(Format::Gpl)),
+            (b"! comment\n255 250 250 snow", Some(Format::X11)),
+            (b"# comment\nsnow rgb(255, 250, 250)", Some(Format::Codi)),
+            (b"\xff\xfe", None),
+            (b"", None),
+        ];
+        for (content, expected) in tests {
+            assert_eq!(Format::detect(content), expected, "input: {content:?}");
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::format;
+
+use super::model::{rgb_from_cmyk, rgb_from_hsb, rgb_from_lab};
+use super::reader::Reader;
+use super::{Error, Palette, Result};
+use crate::color_space::Rgb;
+
+const RGB: u16 = 0;
+const HSB: u16 = 1;
+const CMYK: u16 = 2;
+const LAB: u16 = 7;
+const GRAYSCALE: u16 = 8;
+
+/**
+    Parses Adobe Photoshop color swatches.
+
+    Colors from version 2 section are used if it exists, because only they
+    have names. Colors from version 1 section are named by their hex value.
+
+    Format: <https://www.adobe.com/devnet-apps/photoshop/fileformatashtml/#50577411_pgfId-1055819>
+*/
+pub fn parse(content: &[u8]) -> Result<Palette> {
+    let mut reader = Reader::new(content);
+    let mut palette = Palette::new();
+
+    let mut version = reader.u16()?;
+    if version == 1 {
+        let count = reader.u16()?;
+        for _ in 0..count {
+            let color = read_color(&mut reader)?;
+            palette.push(format!("{color:x}"), color);
+        }
+        if reader.is_empty() {
+            return Ok(palette);
+        }
+        version = reader.u16()?;
+    }
+    if version != 2 {
+        return Err(Error::InvalidHeader);
+    }
+
+    let mut palette = Palette::new();
+    let count = reader.u16()?;
+    for _ in 0..count {
+        let color = read_color(&mut reader)?;
+        let name_len = reader.u32()? as usize;
+        let name = reader.utf16(name_len)?;
+        palette.push(name, color);
+    }
+
+    Ok(palette)
+}
+
+fn read_color(reader: &mut Reader) -> Result<Rgb> {
+    let offset = reader.offset();
+    let space = reader.u16()?;
+    let [w, x, y, z] = [reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?];
+
+    let unit = |val: u16| f32::from(val) / f32::from(u16::MAX);
+    // Lab a* and b* are signed.
+    let signed = |val: u16| f32::from(i16::from_be_bytes(val.to_be_bytes())) / 100.0;
+
+    let color = match space {
+        RGB => Rgb::from_unit([unit(w), unit(x), unit(y)]),
+        HSB => rgb_from_hsb(unit(w) * 360.0, unit(x), unit(y))
+            .ok_or(Error::UnsupportedColorModel { offset })?,
+        // 0 is 100% ink.
+        CMYK => rgb_from_cmyk(1.0 - unit(w), 1.0 - unit(x), 1.0 - unit(y), 1.0 - unit(z)),
+        LAB => rgb_from_lab(f32::from(w) / 100.0, signed(x), signed(y))
+            .ok_or(Error::UnsupportedColorModel { offset })?,
+        // Value from 0 to 10000 is amount of black ink.
+        GRAYSCALE => {
+            let gray = 1.0 - f32::from(w) / 10000.0;
+            Rgb::from_unit([gray, gray, gray])
+        }
+        _ => return Err(Error::UnsupportedColorModel { offset }),
+    };
+    Ok(color)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::color_space::rgb;
+
+    fn color(space: u16, values: [u16; 4]) -> Vec<u8> {
+        let mut res = space.to_be_bytes().to_vec();
+        for val in values {
+            res.extend(val.to_be_bytes());
+        }
+        res
+    }
+
+    fn name(name: &str) -> Vec<u8> {
+        let units: Vec<u16> = name.encode_utf16().chain([0]).collect();
+        let mut res = u32::try_from(units.len()).unwrap().to_be_bytes().to_vec();
+        for unit in units {
+            res.extend(unit.to_be_bytes());
+        }
+        res
+    }
+
+    fn header(version: u16, count: u16) -> Vec<u8> {
+        [version.to_be_bytes(), count.to_be_bytes()].concat()
+    }
+
+    #[test]
+    fn parse_v1() {
+        let content = [
+            header(1, 2),
+            color(RGB, [0xffff, 0, 0, 0]),
+            color(GRAYSCALE, [10000, 0, 0, 0]),
+        ]
+        .concat();
+        assert_eq!(
+            parse(&content),
+            Ok(Palette::from_iter([
+                ("#ff0000", rgb(255, 0, 0)),
+                ("#000000", rgb(0, 0, 0))
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_v1_and_v2() {
+        let red = color(RGB, [0xffff, 0, 0, 0]);
+        let cyan = color(CMYK, [0, 0xffff, 0xffff, 0xffff]);
+        let content = [
+            header(1, 2),
+            red.clone(),
+            cyan.clone(),
+            header(2, 2),
+            red,
+            name("Red"),
+            cyan,
+            name("Cyan"),
+        ]
+        .concat();
+        assert_eq!(
+            parse(&content),
+            Ok(Palette::from_iter([
+                ("Red", rgb(255, 0, 0)),
+                ("Cyan", rgb(0, 255, 255))
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_other_color_spaces() {
+        let content = [
+            header(2, 2),
+            color(HSB, [0x5555, 0xffff, 0xffff, 0]),
+            name("Green"),
+            color(LAB, [10000, 0, 0, 0]),
+            name("White"),
+        ]
+        .concat();
+        assert_eq!(
+            parse(&content),
+            Ok(Palette::from_iter([
+                ("Green", rgb(0, 255, 0)),
+                ("White", rgb(255, 255, 255))
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(parse(&header(3, 0)), Err(Error::InvalidHeader));
+        assert_eq!(
+            parse(&[header(1, 1), color(RGB, [0; 4])[..6].to_vec()].concat()),
+            Err(Error::UnexpectedEof { offset: 10 })
+        );
+        assert_eq!(
+            parse(&[header(1, 1), color(3, [0; 4])].concat()),
+            Err(Error::UnsupportedColorModel { offset: 4 })
+        );
+    }
+}
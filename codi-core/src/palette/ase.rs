@@ -0,0 +1,139 @@
+use super::model::{rgb_from_cmyk, rgb_from_lab};
+use super::reader::Reader;
+use super::{Error, Palette, Result};
+use crate::color_space::Rgb;
+
+pub const MAGIC: &[u8] = b"ASEF";
+
+const COLOR_ENTRY: u16 = 0x0001;
+
+/**
+    Parses Adobe swatch exchange file. Groups are flattened.
+
+    Format: <http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase>
+*/
+pub fn parse(content: &[u8]) -> Result<Palette> {
+    let mut reader = Reader::new(content);
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(Error::InvalidHeader);
+    }
+    let _version = (reader.u16()?, reader.u16()?);
+    let block_count = reader.u32()?;
+
+    let mut palette = Palette::new();
+    for _ in 0..block_count {
+        let block_type = reader.u16()?;
+        let block_len = reader.u32()? as usize;
+        let mut block = Reader::new(reader.bytes(block_len)?);
+        // Group start and end blocks don't have colors.
+        if block_type != COLOR_ENTRY {
+            continue;
+        }
+
+        let name_len = usize::from(block.u16()?);
+        let name = block.utf16(name_len)?;
+        // Offset of the model inside the whole file.
+        let offset = reader.offset() - block_len + block.offset();
+        let color = read_color(&mut block).map_err(|err| match err {
+            Error::UnexpectedEof { .. } => Error::UnexpectedEof {
+                offset: reader.offset(),
+            },
+            _ => Error::UnsupportedColorModel { offset },
+        })?;
+        palette.push(name, color);
+    }
+
+    Ok(palette)
+}
+
+fn read_color(block: &mut Reader) -> Result<Rgb> {
+    let unsupported = Error::UnsupportedColorModel { offset: 0 };
+    let color = match &block.array::<4>()? {
+        b"RGB " => Rgb::from_unit([block.f32()?, block.f32()?, block.f32()?]),
+        b"CMYK" => rgb_from_cmyk(block.f32()?, block.f32()?, block.f32()?, block.f32()?),
+        // Lightness is stored in range [0, 1].
+        b"LAB " => {
+            rgb_from_lab(block.f32()? * 100.0, block.f32()?, block.f32()?).ok_or(unsupported)?
+        }
+        b"Gray" => {
+            let gray = block.f32()?;
+            Rgb::from_unit([gray, gray, gray])
+        }
+        _ => return Err(unsupported),
+    };
+    Ok(color)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::color_space::rgb;
+
+    fn header(block_count: u32) -> Vec<u8> {
+        [MAGIC, &[0, 1, 0, 0], &block_count.to_be_bytes()[..]].concat()
+    }
+
+    fn block(block_type: u16, data: &[u8]) -> Vec<u8> {
+        let len = u32::try_from(data.len()).unwrap();
+        [&block_type.to_be_bytes()[..], &len.to_be_bytes(), data].concat()
+    }
+
+    fn color(name: &str, model: [u8; 4], values: &[f32]) -> Vec<u8> {
+        let units: Vec<u16> = name.encode_utf16().chain([0]).collect();
+        let mut data = u16::try_from(units.len()).unwrap().to_be_bytes().to_vec();
+        for unit in units {
+            data.extend(unit.to_be_bytes());
+        }
+        data.extend(model);
+        for val in values {
+            data.extend(val.to_be_bytes());
+        }
+        // Color type: global, spot or normal.
+        data.extend(2u16.to_be_bytes());
+        block(COLOR_ENTRY, &data)
+    }
+
+    #[test]
+    fn parse_ok() {
+        let content = [
+            header(6),
+            block(0xc001, &[0, 2, 0, b'G', 0, 0]),
+            color("Red", *b"RGB ", &[1.0, 0.0, 0.0]),
+            color("Cyan", *b"CMYK", &[1.0, 0.0, 0.0, 0.0]),
+            color("White", *b"LAB ", &[1.0, 0.0, 0.0]),
+            color("Gray", *b"Gray", &[0.5]),
+            block(0xc002, &[]),
+        ]
+        .concat();
+        assert_eq!(
+            parse(&content),
+            Ok(Palette::from_iter([
+                ("Red", rgb(255, 0, 0)),
+                ("Cyan", rgb(0, 255, 255)),
+                ("White", rgb(255, 255, 255)),
+                ("Gray", rgb(128, 128, 128)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(parse(b"ASEX\0\x01\0\0"), Err(Error::InvalidHeader));
+
+        let truncated = [header(2), color("Red", *b"RGB ", &[1.0, 0.0, 0.0])].concat();
+        assert_eq!(
+            parse(&truncated),
+            Err(Error::UnexpectedEof {
+                offset: truncated.len()
+            })
+        );
+
+        let unknown = [header(1), color("Red", *b"HSV ", &[1.0, 0.0, 0.0])].concat();
+        assert_eq!(
+            parse(&unknown),
+            Err(Error::UnsupportedColorModel { offset: 28 })
+        );
+    }
+}
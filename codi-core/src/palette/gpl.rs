@@ -0,0 +1,74 @@
+use alloc::format;
+
+use super::{Error, Palette, Result};
+
+pub const MAGIC: &str = "GIMP Palette";
+
+/**
+    Parses GIMP palette.
+
+    Format: <https://developer.gimp.org/core/standards/gpl/>
+*/
+pub fn parse(content: &str) -> Result<Palette> {
+    let mut lines = content.lines().enumerate();
+    if lines.next().map(|(_, line)| line.trim_end()) != Some(MAGIC) {
+        return Err(Error::InvalidHeader);
+    }
+
+    let mut palette = Palette::new();
+    for (idx, line) in lines {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("Name:")
+            || line.starts_with("Columns:")
+        {
+            continue;
+        }
+
+        let (color, name) =
+            super::x11::parse_line(line).ok_or(Error::InvalidColor { line: idx + 1 })?;
+        // GIMP allows colors without names.
+        if name.is_empty() {
+            palette.push(format!("{color:x}"), color);
+        } else {
+            palette.push(name, color);
+        }
+    }
+
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::rgb;
+
+    #[test]
+    fn parse_ok() {
+        let content = "\
+GIMP Palette
+Name: Brand
+Columns: 4
+#
+220  20  60	Brand Red
+  0   0  95
+";
+        assert_eq!(
+            parse(content),
+            Ok(Palette::from_iter([
+                ("Brand Red", rgb(220, 20, 60)),
+                ("#00005f", rgb(0, 0, 95)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(parse("220 20 60 red"), Err(Error::InvalidHeader));
+        assert_eq!(
+            parse("GIMP Palette\nName: Brand\n220 20 red"),
+            Err(Error::InvalidColor { line: 3 })
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::color_space::{Cielab, Hsv, Rgb};
+
+/**
+    Naive CMYK conversion without color profile, channels are in range [0, 1].
+*/
+pub fn rgb_from_cmyk(c: f32, m: f32, y: f32, k: f32) -> Rgb {
+    Rgb::from_unit([
+        (1.0 - c) * (1.0 - k),
+        (1.0 - m) * (1.0 - k),
+        (1.0 - y) * (1.0 - k),
+    ])
+}
+
+/**
+    Hue is in degrees, saturation and brightness are in range [0, 1].
+    Returns [`Option::None`] if any of the values is NaN.
+*/
+pub fn rgb_from_hsb(hue: f32, sat: f32, bri: f32) -> Option<Rgb> {
+    Hsv::new(hue, sat, bri).ok().map(Rgb::from)
+}
+
+/**
+    Returns [`Option::None`] if any of the values is NaN.
+*/
+pub fn rgb_from_lab(l: f32, a: f32, b: f32) -> Option<Rgb> {
+    Cielab::new(l, a, b).ok().map(Rgb::from)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::rgb;
+
+    #[test]
+    fn hsb_primaries() {
+        assert_eq!(rgb_from_hsb(0.0, 1.0, 1.0), Some(rgb(255, 0, 0)));
+        assert_eq!(rgb_from_hsb(120.0, 1.0, 1.0), Some(rgb(0, 255, 0)));
+        assert_eq!(rgb_from_hsb(240.0, 1.0, 1.0), Some(rgb(0, 0, 255)));
+        assert_eq!(rgb_from_hsb(360.0, 1.0, 1.0), Some(rgb(255, 0, 0)));
+        assert_eq!(rgb_from_hsb(0.0, 0.0, 0.5), Some(rgb(128, 128, 128)));
+    }
+
+    #[test]
+    fn cmyk_primaries() {
+        assert_eq!(rgb_from_cmyk(0.0, 0.0, 0.0, 0.0), rgb(255, 255, 255));
+        assert_eq!(rgb_from_cmyk(0.0, 0.0, 0.0, 1.0), rgb(0, 0, 0));
+        assert_eq!(rgb_from_cmyk(1.0, 0.0, 0.0, 0.0), rgb(0, 255, 255));
+    }
+}
@@ -0,0 +1,92 @@
+use alloc::string::String;
+
+use super::{Error, Result};
+
+/**
+    Cursor over big-endian binary palette data.
+*/
+pub struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset.checked_add(len).ok_or_else(|| self.eof())?;
+        let bytes = self.data.get(self.offset..end).ok_or_else(|| self.eof())?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self
+            .bytes(N)?
+            .try_into()
+            .expect("SAFETY: slice has exactly N bytes"))
+    }
+
+    pub fn u16(&mut self) -> Result<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub fn u32(&mut self) -> Result<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub fn f32(&mut self) -> Result<f32> {
+        self.array().map(f32::from_be_bytes)
+    }
+
+    /**
+        Reads `len` UTF-16 code units. Trailing null terminator is dropped and
+        invalid surrogates are replaced with [`char::REPLACEMENT_CHARACTER`].
+    */
+    pub fn utf16(&mut self, len: usize) -> Result<String> {
+        let bytes = self.bytes(len.checked_mul(2).ok_or_else(|| self.eof())?)?;
+        let units = bytes
+            .chunks_exact(2)
+            .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+        Ok(char::decode_utf16(units)
+            .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .take_while(|ch| *ch != '\0')
+            .collect())
+    }
+
+    /**
+        Error for the read past the end of the data, lengths that overflow
+        are treated the same way.
+    */
+    const fn eof(&self) -> Error {
+        Error::UnexpectedEof {
+            offset: self.data.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overflowing_lengths() {
+        let eof = Error::UnexpectedEof { offset: 4 };
+        let mut reader = Reader::new(&[0, 1, 2, 3]);
+        assert_eq!(reader.bytes(2), Ok(&[0, 1][..]));
+        assert_eq!(reader.bytes(usize::MAX), Err(eof));
+        assert_eq!(reader.utf16(usize::MAX / 2 + 1), Err(eof));
+        assert_eq!(reader.offset(), 2);
+        assert_eq!(reader.u16(), Ok(0x0203));
+    }
+}
@@ -0,0 +1,90 @@
+use super::{Error, Palette, Result};
+use crate::color_space::Rgb;
+
+/**
+    Parses X11 rgb.txt.
+
+    Line format: "240 248 255 alice blue". Comments start with '!'.
+*/
+pub fn parse(content: &str) -> Result<Palette> {
+    let mut palette = Palette::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('!') {
+            continue;
+        }
+
+        let (color, name) = parse_line(line).ok_or(Error::InvalidColor { line: idx + 1 })?;
+        if name.is_empty() {
+            return Err(Error::InvalidColor { line: idx + 1 });
+        }
+        palette.push(name, color);
+    }
+
+    Ok(palette)
+}
+
+/**
+    Parses "r g b name" line, where name can contain spaces or be empty.
+*/
+pub(super) fn parse_line(line: &str) -> Option<(Rgb, &str)> {
+    let mut rest = line;
+    let mut channel = || {
+        let trimmed = rest.trim_start();
+        let end = trimmed
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(trimmed.len());
+        let (value, tail) = trimmed.split_at(end);
+        // Channel must be separated from the next token.
+        if !(tail.is_empty() || tail.starts_with(char::is_whitespace)) {
+            return None;
+        }
+        rest = tail;
+        value.parse::<u8>().ok()
+    };
+
+    let color = Rgb::new(channel()?, channel()?, channel()?);
+    Some((color, rest.trim()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::rgb;
+
+    #[test]
+    fn parse_ok() {
+        let content = "\
+! $Xorg: rgb.txt,v 1.3 2000/08/17 19:54:00 cpqbld Exp $
+255 250 250		snow
+248 248 255		ghost white
+  0   0   0		black
+";
+        assert_eq!(
+            parse(content),
+            Ok(Palette::from_iter([
+                ("snow", rgb(255, 250, 250)),
+                ("ghost white", rgb(248, 248, 255)),
+                ("black", rgb(0, 0, 0)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let tests = [
+            "255 250 250",
+            "255 250 snow",
+            "255 250 256 snow",
+            "255 250 250snow",
+            "\n-1 0 0 snow",
+        ];
+        for content in tests {
+            assert!(
+                matches!(parse(content), Err(Error::InvalidColor { .. })),
+                "input: {content}"
+            );
+        }
+    }
+}
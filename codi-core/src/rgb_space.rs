@@ -0,0 +1,431 @@
+use libm::powf;
+
+use crate::color_space::{Error, Rgb, Xyz};
+use crate::illuminant::{Adaptation, Illuminant};
+use crate::math_utils::{column, matrix_inverse, matrix_mul, matrix_mul_f32};
+
+const PQ_M1: f32 = 2610.0 / 16384.0;
+pub(crate) const PQ_M2: f32 = 2523.0 / 32.0;
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 128.0;
+const PQ_C3: f32 = 2392.0 / 128.0;
+
+/**
+    Conversion between linear light and encoded channel values. Negative
+    values are converted symmetrically, as CSS Color 4 does.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFunction {
+    /// Channels are already linear.
+    Linear,
+    /// sRGB curve, also used by Display P3.
+    Srgb,
+    /// Pure power function with the given exponent.
+    Gamma(f32),
+    /// ITU-R BT.2020 curve.
+    Rec2020,
+    /// ROMM RGB curve with linear segment near black.
+    ProPhoto,
+    /// SMPTE ST 2084 perceptual quantizer for HDR content, linear value
+    /// `1.0` corresponds to the luminance of 10000 cd/m².
+    Pq,
+}
+
+impl TransferFunction {
+    /**
+        Converts encoded channel value into linear light.
+    */
+    pub fn decode(self, value: f32) -> f32 {
+        const REC2020_ALPHA: f32 = 1.099_296_8;
+        const REC2020_BETA: f32 = 0.018_053_968;
+
+        let abs = value.abs();
+        let linear = match self {
+            Self::Linear => abs,
+            Self::Srgb if abs <= 0.04045 => abs / 12.92,
+            Self::Srgb => powf((abs + 0.055) / 1.055, 2.4),
+            Self::Gamma(gamma) => powf(abs, gamma),
+            Self::Rec2020 if abs < REC2020_BETA * 4.5 => abs / 4.5,
+            Self::Rec2020 => powf((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA, 1.0 / 0.45),
+            Self::ProPhoto if abs < 16.0 / 512.0 => abs / 16.0,
+            Self::ProPhoto => powf(abs, 1.8),
+            Self::Pq => pq_decode(abs, PQ_M2),
+        };
+        linear.copysign(value)
+    }
+
+    /**
+        Converts linear light into encoded channel value.
+    */
+    pub fn encode(self, value: f32) -> f32 {
+        const REC2020_ALPHA: f32 = 1.099_296_8;
+        const REC2020_BETA: f32 = 0.018_053_968;
+
+        let abs = value.abs();
+        let encoded = match self {
+            Self::Linear => abs,
+            Self::Srgb if abs <= 0.003_130_8 => abs * 12.92,
+            Self::Srgb => powf(abs, 1.0 / 2.4) * 1.055 - 0.055,
+            Self::Gamma(gamma) => powf(abs, 1.0 / gamma),
+            Self::Rec2020 if abs < REC2020_BETA => abs * 4.5,
+            Self::Rec2020 => REC2020_ALPHA * powf(abs, 0.45) - (REC2020_ALPHA - 1.0),
+            Self::ProPhoto if abs < 1.0 / 512.0 => abs * 16.0,
+            Self::ProPhoto => powf(abs, 1.0 / 1.8),
+            Self::Pq => pq_encode(abs, PQ_M2),
+        };
+        encoded.copysign(value)
+    }
+}
+
+/**
+    Perceptual quantizer of the non-negative linear `value` with the exponent
+    `m2`, which is changed by some color spaces.
+*/
+pub(crate) fn pq_encode(value: f32, m2: f32) -> f32 {
+    let val = powf(value, PQ_M1);
+    powf((PQ_C1 + PQ_C2 * val) / (1.0 + PQ_C3 * val), m2)
+}
+
+/**
+    Inverse of [`pq_encode`].
+*/
+pub(crate) fn pq_decode(value: f32, m2: f32) -> f32 {
+    let val = powf(value, 1.0 / m2);
+    powf((val - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * val), 1.0 / PQ_M1)
+}
+
+/**
+    RGB working space defined by chromaticities of its primaries, reference
+    white and transfer function. Channels are in range [0, 1] for the colors
+    inside of the gamut.
+
+    # Example
+
+    ```
+    use codi_core::html_color::find_closest;
+    use codi_core::color_dist::CIEDE2000;
+    use codi_core::rgb_space::RgbSpace;
+
+    let color = RgbSpace::DISPLAY_P3.to_rgb([0.3, 0.6, 0.4]).unwrap();
+    assert_eq!(find_closest(&CIEDE2000::default(), color).color.name, "seagreen");
+    ```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RgbSpace {
+    name: &'static str,
+    primaries: [[f32; 2]; 3],
+    white: Illuminant,
+    transfer: TransferFunction,
+    /// Converts linear channels into XYZ relative to D65 white.
+    pub(crate) linear_to_xyz: [[f32; 3]; 3],
+    /// Inverse of `linear_to_xyz`.
+    pub(crate) xyz_to_linear: [[f32; 3]; 3],
+}
+
+impl RgbSpace {
+    pub const SRGB: Self = Self::new(
+        "srgb",
+        [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+        Illuminant::D65,
+        TransferFunction::Srgb,
+    );
+    pub const SRGB_LINEAR: Self = Self::new(
+        "srgb-linear",
+        Self::SRGB.primaries,
+        Self::SRGB.white,
+        TransferFunction::Linear,
+    );
+    pub const DISPLAY_P3: Self = Self::new(
+        "display-p3",
+        [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+        Illuminant::D65,
+        TransferFunction::Srgb,
+    );
+    pub const REC2020: Self = Self::new(
+        "rec2020",
+        [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]],
+        Illuminant::D65,
+        TransferFunction::Rec2020,
+    );
+    /// Adobe RGB (1998).
+    pub const ADOBE_RGB: Self = Self::new(
+        "a98-rgb",
+        [[0.64, 0.33], [0.21, 0.71], [0.15, 0.06]],
+        Illuminant::D65,
+        TransferFunction::Gamma(563.0 / 256.0),
+    );
+    pub const PROPHOTO_RGB: Self = Self::new(
+        "prophoto-rgb",
+        [
+            [0.734_699, 0.265_301],
+            [0.159_597, 0.840_403],
+            [0.036_598, 0.000_105],
+        ],
+        Illuminant::D50,
+        TransferFunction::ProPhoto,
+    );
+
+    pub const ALL: [Self; 6] = [
+        Self::SRGB,
+        Self::SRGB_LINEAR,
+        Self::DISPLAY_P3,
+        Self::REC2020,
+        Self::ADOBE_RGB,
+        Self::PROPHOTO_RGB,
+    ];
+
+    /**
+        Find built-in space by its name, case-insensitive.
+    */
+    pub fn find(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|space| space.name().eq_ignore_ascii_case(name))
+    }
+
+    /**
+        Creates space from the chromaticity coordinates `(x, y)` of the red,
+        green and blue `primaries`, `name` is used as in CSS `color()`
+        function. Conversion matrices are computed here, so the constant
+        spaces convert colors without recomputing them.
+    */
+    pub const fn new(
+        name: &'static str,
+        primaries: [[f32; 2]; 3],
+        white: Illuminant,
+        transfer: TransferFunction,
+    ) -> Self {
+        let to_xyz = Self::xyz_matrix(primaries, white);
+        let linear_to_xyz = if matches!(white, Illuminant::D65) {
+            to_xyz
+        } else {
+            matrix_mul_f32(
+                &Adaptation::Bradford.matrix(white, Illuminant::D65),
+                &to_xyz,
+            )
+        };
+        Self {
+            name,
+            primaries,
+            white,
+            transfer,
+            linear_to_xyz,
+            xyz_to_linear: matrix_inverse(&linear_to_xyz),
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub const fn primaries(&self) -> [[f32; 2]; 3] {
+        self.primaries
+    }
+
+    pub const fn white(&self) -> Illuminant {
+        self.white
+    }
+
+    pub const fn transfer(&self) -> TransferFunction {
+        self.transfer
+    }
+
+    /**
+        Matrix that converts linear channels into XYZ relative to the white
+        of this space.
+
+        <http://www.brucelindbloom.com/index.html?Eqn_RGB_XYZ_Matrix.html>
+    */
+    pub const fn to_xyz_matrix(&self) -> [[f32; 3]; 3] {
+        Self::xyz_matrix(self.primaries, self.white)
+    }
+
+    const fn xyz_matrix(primaries: [[f32; 2]; 3], white: Illuminant) -> [[f32; 3]; 3] {
+        let [[xr, yr], [xg, yg], [xb, yb]] = primaries;
+        let primaries = [
+            [xr / yr, xg / yg, xb / yb],
+            [1.0, 1.0, 1.0],
+            [
+                (1.0 - xr - yr) / yr,
+                (1.0 - xg - yg) / yg,
+                (1.0 - xb - yb) / yb,
+            ],
+        ];
+        let scale = matrix_mul_f32(&matrix_inverse(&primaries), &column(white.tristimulus()));
+
+        let mut res = primaries;
+        let mut row = 0;
+        while row < 3 {
+            let mut col = 0;
+            while col < 3 {
+                res[row][col] *= scale[col][0];
+                col += 1;
+            }
+            row += 1;
+        }
+        res
+    }
+
+    /**
+        Converts encoded channels into XYZ relative to D65 white, colors of
+        spaces with other white are adapted with Bradford transform.
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the channels is NaN or
+        [`Error::NotFinite`] if any of them is infinite or too large.
+    */
+    pub fn to_xyz(&self, channels: [f32; 3]) -> Result<Xyz, Error> {
+        let linear = channels.map(|col| [self.transfer.decode(col)]);
+        let res = matrix_mul(&self.linear_to_xyz, &linear);
+        Xyz::new(res[0][0], res[1][0], res[2][0])
+    }
+
+    /**
+        Converts XYZ relative to D65 white into encoded channels. Channels of
+        the colors outside of the gamut are out of range [0, 1].
+    */
+    pub fn from_xyz(&self, xyz: Xyz) -> [f32; 3] {
+        let res = matrix_mul(&self.xyz_to_linear, &[[xyz.x()], [xyz.y()], [xyz.z()]]);
+        [res[0][0], res[1][0], res[2][0]].map(|col| self.transfer.encode(col))
+    }
+
+    /**
+        Converts encoded channels into 8-bit sRGB color, colors outside of the
+        sRGB gamut are clipped. Use [`GamutMapping`](crate::gamut::GamutMapping)
+        with [`Self::to_xyz`] to map them.
+
+        # Errors
+
+        Returns [`Error::NotANumber`] if any of the channels is NaN or
+        [`Error::NotFinite`] if any of them is infinite or too large.
+    */
+    pub fn to_rgb(&self, channels: [f32; 3]) -> Result<Rgb, Error> {
+        self.to_xyz(channels).map(Rgb::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::rgb;
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3], tolerance: f32) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < tolerance),
+            "got: {actual:?}, expected: {expected:?}"
+        );
+    }
+
+    #[test]
+    fn transfer_round_trip() {
+        let functions = [
+            TransferFunction::Linear,
+            TransferFunction::Srgb,
+            TransferFunction::Gamma(2.2),
+            TransferFunction::Rec2020,
+            TransferFunction::ProPhoto,
+            TransferFunction::Pq,
+        ];
+        for function in functions {
+            for value in [-0.5, 0.0, 0.001, 0.01, 0.2, 0.5, 1.0, 1.2] {
+                let actual = function.encode(function.decode(value));
+                assert!((actual - value).abs() < 1e-5, "{function:?}: {value}");
+            }
+        }
+
+        // 100 cd/m² reference white of SDR content.
+        assert!((TransferFunction::Pq.encode(0.01) - 0.508_1).abs() < 1e-4);
+        assert!((TransferFunction::Pq.decode(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn srgb_matrix() {
+        // http://www.brucelindbloom.com/index.html?Eqn_RGB_XYZ_Matrix.html
+        let expected = [
+            [0.412_456_4, 0.357_576_1, 0.180_437_5],
+            [0.212_672_9, 0.715_152_2, 0.072_175],
+            [0.019_333_9, 0.119_192, 0.950_304_1],
+        ];
+        let actual = RgbSpace::SRGB.to_xyz_matrix();
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert_close(actual, expected, 1e-3);
+        }
+    }
+
+    #[test]
+    fn white_and_black() {
+        for space in RgbSpace::ALL {
+            assert_eq!(space.to_rgb([1.0; 3]), Ok(rgb(255, 255, 255)), "{space:?}");
+            assert_eq!(space.to_rgb([0.0; 3]), Ok(rgb(0, 0, 0)), "{space:?}");
+            let white = Illuminant::D65.white_point();
+            assert_close(space.from_xyz(white), [1.0; 3], 1e-3);
+        }
+    }
+
+    #[test]
+    fn wide_gamut_reference_values() {
+        // sRGB primaries in other spaces, checked against Lindbloom matrices.
+        let tests = [
+            (
+                RgbSpace::DISPLAY_P3,
+                rgb(255, 0, 0),
+                [0.917_5, 0.200_2, 0.138_6],
+            ),
+            (
+                RgbSpace::REC2020,
+                rgb(255, 0, 0),
+                [0.791_9, 0.230_9, 0.073_8],
+            ),
+            (RgbSpace::ADOBE_RGB, rgb(0, 255, 0), [0.565_0, 1.0, 0.234_5]),
+            (
+                RgbSpace::PROPHOTO_RGB,
+                rgb(0, 0, 255),
+                [0.336_2, 0.137_6, 0.922_9],
+            ),
+        ];
+        for (space, color, expected) in tests {
+            let channels = space.from_xyz(Xyz::from(color));
+            assert_close(channels, expected, 2e-3);
+            assert_eq!(space.to_rgb(channels), Ok(color), "{}", space.name());
+        }
+    }
+
+    #[test]
+    fn srgb_conversions_use_same_matrix() {
+        let (red, coral) = (rgb(255, 0, 0), rgb(255, 127, 80));
+        assert_close(
+            RgbSpace::SRGB.from_xyz(Xyz::from(red)),
+            [1.0, 0.0, 0.0],
+            1e-5,
+        );
+        assert_close(
+            RgbSpace::SRGB.from_xyz(Xyz::from(coral)),
+            [1.0, 127.0 / 255.0, 80.0 / 255.0],
+            1e-5,
+        );
+        // Adobe RGB has the same red and blue primaries as sRGB, gamma
+        // magnifies rounding errors of the zero channels.
+        let adobe_red = RgbSpace::ADOBE_RGB.from_xyz(Xyz::from(red));
+        assert_close(adobe_red, [0.858_6, 0.0, 0.0], 1e-3);
+    }
+
+    #[test]
+    fn find_by_name() {
+        for space in RgbSpace::ALL {
+            assert_eq!(RgbSpace::find(&space.name().to_uppercase()), Some(space));
+        }
+        assert_eq!(RgbSpace::find("p3"), None);
+        assert_eq!(
+            RgbSpace::SRGB.to_xyz([f32::NAN, 0.0, 0.0]),
+            Err(Error::NotANumber)
+        );
+        assert_eq!(
+            RgbSpace::DISPLAY_P3.to_xyz([0.0, f32::INFINITY, 0.0]),
+            Err(Error::NotFinite)
+        );
+    }
+}